@@ -0,0 +1,109 @@
+//! Benchmarks for the game's hot path: `Game::update` over many ticks and
+//! `Game::spawn_food` on a nearly full board, with and without wall
+//! wrapping. Run with `cargo bench`.
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use snake_rs::{Direction, Game, GameState, Position, Scenario};
+use std::hint::black_box;
+
+const BOARD_WIDTH: u16 = 30;
+const BOARD_HEIGHT: u16 = 20;
+const TICKS: usize = 1_000;
+/// Cells left free when measuring food spawning.
+const FREE_CELLS: usize = 3;
+
+/// Turns clockwise every few ticks so the snake keeps circling instead of
+/// running straight into a wall.
+fn steering(tick: usize) -> Option<Direction> {
+    const TURNS: [Direction; 4] = [
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+        Direction::Up,
+    ];
+    tick.is_multiple_of(7)
+        .then(|| TURNS[tick / 7 % TURNS.len()])
+}
+
+fn bench_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update");
+    for wrapping in [true, false] {
+        group.bench_with_input(
+            BenchmarkId::new("ticks", if wrapping { "wrapping" } else { "walls" }),
+            &wrapping,
+            |b, &wrapping| {
+                b.iter_batched(
+                    || {
+                        let mut game = Game::with_seed(BOARD_WIDTH, BOARD_HEIGHT, 7);
+                        game.set_wall_wrapping(wrapping);
+                        game
+                    },
+                    |mut game| {
+                        for tick in 0..TICKS {
+                            if game.state() != GameState::Playing {
+                                game.reset();
+                            }
+                            if let Some(direction) = steering(tick) {
+                                game.set_direction(direction);
+                            }
+                            black_box(game.update());
+                        }
+                        game
+                    },
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+    }
+    group.finish();
+}
+
+/// A snake winding back and forth over every row, leaving only the last
+/// `FREE_CELLS` cells of the board free.
+fn nearly_full_board() -> Scenario {
+    let mut body: Vec<Position> = (0..BOARD_HEIGHT)
+        .flat_map(|y| {
+            let row = (0..BOARD_WIDTH).map(move |x| Position::new(x, y));
+            let row: Vec<Position> = if y.is_multiple_of(2) {
+                row.collect()
+            } else {
+                row.rev().collect()
+            };
+            row
+        })
+        .collect();
+    body.truncate(body.len() - FREE_CELLS);
+    body.reverse();
+    // The bottom row is walked right to left when the height is even
+    let direction = if BOARD_HEIGHT.is_multiple_of(2) {
+        Direction::Left
+    } else {
+        Direction::Right
+    };
+
+    Scenario {
+        board_width: BOARD_WIDTH,
+        board_height: BOARD_HEIGHT,
+        body,
+        direction,
+        food: None,
+        score: 0,
+    }
+}
+
+fn bench_spawn_food(c: &mut Criterion) {
+    let scenario = nearly_full_board();
+    let mut group = c.benchmark_group("spawn_food");
+    for wrapping in [true, false] {
+        let mut game = Game::from_scenario(&scenario).expect("the board is a valid scenario");
+        game.set_wall_wrapping(wrapping);
+        group.bench_function(
+            BenchmarkId::new("nearly_full", if wrapping { "wrapping" } else { "walls" }),
+            |b| b.iter(|| black_box(&mut game).spawn_food()),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_update, bench_spawn_food);
+criterion_main!(benches);
@@ -0,0 +1,101 @@
+//! Plays a seeded game without a terminal UI and prints the board after
+//! every tick.
+//!
+//! The first word on stdin is the seed. Every following character is one
+//! tick: `U`, `D`, `L` or `R` turns the snake, `.` keeps its heading, and
+//! whitespace is ignored. The game runs until the script ends or the game
+//! does, then prints the final score.
+//!
+//! ```text
+//! echo "42 ..DD.LL" | cargo run --example simulate
+//! ```
+
+use snake_rs::{Direction, Game, GameState};
+use std::io::{self, Read, Write};
+
+const BOARD_WIDTH: u16 = 20;
+const BOARD_HEIGHT: u16 = 10;
+
+/// One entry per tick: the turn to make, if any.
+fn parse_script(script: &str) -> io::Result<Vec<Option<Direction>>> {
+    script
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c.to_ascii_uppercase() {
+            'U' => Ok(Some(Direction::Up)),
+            'D' => Ok(Some(Direction::Down)),
+            'L' => Ok(Some(Direction::Left)),
+            'R' => Ok(Some(Direction::Right)),
+            '.' => Ok(None),
+            other => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unknown move '{other}', expected U, D, L, R or ."),
+            )),
+        })
+        .collect()
+}
+
+/// Runs the seed and script in `input`, writing each tick's board to `out`,
+/// and returns the final score.
+fn simulate(input: &str, out: &mut impl Write) -> io::Result<u32> {
+    let (seed, script) = input
+        .trim_start()
+        .split_once(char::is_whitespace)
+        .unwrap_or((input.trim(), ""));
+    let seed = seed
+        .parse()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "expected a seed first"))?;
+    let moves = parse_script(script)?;
+
+    let mut game = Game::with_seed(BOARD_WIDTH, BOARD_HEIGHT, seed);
+    writeln!(out, "{}", game.to_text_grid())?;
+    for (tick, turn) in moves.into_iter().enumerate() {
+        if game.state() != GameState::Playing {
+            break;
+        }
+        if let Some(direction) = turn {
+            game.set_direction(direction);
+        }
+        let event = game.update();
+        writeln!(out, "Tick {}: {:?}", tick + 1, event)?;
+        writeln!(out, "{}", game.to_text_grid())?;
+    }
+
+    writeln!(out, "Final score: {}", game.score())?;
+    Ok(game.score())
+}
+
+fn main() -> io::Result<()> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    simulate(&input, &mut io::stdout().lock())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_ends_with_final_score() {
+        let mut out = Vec::new();
+        let score = simulate("42\n..D.L", &mut out).unwrap();
+
+        let mut game = Game::with_seed(BOARD_WIDTH, BOARD_HEIGHT, 42);
+        let mut moves = [Direction::Right; 5];
+        moves[2..4].fill(Direction::Down);
+        moves[4] = Direction::Left;
+        game.run_steps(5, &moves);
+        assert_eq!(score, game.score());
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Tick 5:"));
+        assert!(out.ends_with(&format!("Final score: {}\n", score)));
+    }
+
+    #[test]
+    fn test_unknown_move_is_rejected() {
+        assert!(simulate("1 UX", &mut Vec::new()).is_err());
+        assert!(simulate("seed U", &mut Vec::new()).is_err());
+    }
+}
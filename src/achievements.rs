@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+/// Number of ticks an achievement toast stays on screen.
+const TOAST_TICKS: u32 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Milestone {
+    Score(u32),
+    Length(usize),
+    Survival(Duration),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Achievement {
+    pub name: String,
+    pub milestone: Milestone,
+}
+
+impl Achievement {
+    pub fn new(name: impl Into<String>, milestone: Milestone) -> Self {
+        Self {
+            name: name.into(),
+            milestone,
+        }
+    }
+
+    fn is_reached(&self, score: u32, length: usize, survived: Duration) -> bool {
+        match self.milestone {
+            Milestone::Score(target) => score >= target,
+            Milestone::Length(target) => length >= target,
+            Milestone::Survival(target) => survived >= target,
+        }
+    }
+}
+
+/// Tracks which achievements were reached in the current game and the
+/// toast announcing the most recent one.
+pub struct Achievements {
+    milestones: Vec<Achievement>,
+    triggered: Vec<bool>,
+    toast: Option<(usize, u32)>,
+}
+
+impl Default for Achievements {
+    fn default() -> Self {
+        Self::new(vec![
+            Achievement::new("First 100 points!", Milestone::Score(100)),
+            Achievement::new("Length 20!", Milestone::Length(20)),
+            Achievement::new(
+                "Survived 2 minutes",
+                Milestone::Survival(Duration::from_secs(120)),
+            ),
+        ])
+    }
+}
+
+impl Achievements {
+    pub fn new(milestones: Vec<Achievement>) -> Self {
+        let triggered = vec![false; milestones.len()];
+        Self {
+            milestones,
+            triggered,
+            toast: None,
+        }
+    }
+
+    /// Marks every newly reached achievement as triggered and returns them.
+    /// Each achievement fires at most once until `reset` is called.
+    pub fn check(&mut self, score: u32, length: usize, survived: Duration) -> Vec<&Achievement> {
+        let mut newly_triggered = Vec::new();
+
+        for (i, achievement) in self.milestones.iter().enumerate() {
+            if !self.triggered[i] && achievement.is_reached(score, length, survived) {
+                self.triggered[i] = true;
+                self.toast = Some((i, TOAST_TICKS));
+                newly_triggered.push(achievement);
+            }
+        }
+
+        newly_triggered
+    }
+
+    pub fn is_triggered(&self, name: &str) -> bool {
+        self.milestones
+            .iter()
+            .zip(&self.triggered)
+            .any(|(achievement, &triggered)| triggered && achievement.name == name)
+    }
+
+    /// Message of the toast currently on screen, if any.
+    pub fn toast(&self) -> Option<&str> {
+        self.toast
+            .map(|(index, _)| self.milestones[index].name.as_str())
+    }
+
+    /// Advances the toast timer by one tick, hiding it once it expires.
+    pub fn tick(&mut self) {
+        if let Some((index, ticks_remaining)) = self.toast {
+            self.toast = ticks_remaining
+                .checked_sub(1)
+                .filter(|&remaining| remaining > 0)
+                .map(|remaining| (index, remaining));
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.triggered
+            .iter_mut()
+            .for_each(|triggered| *triggered = false);
+        self.toast = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_achievement_triggers_exactly_once() {
+        let mut achievements = Achievements::default();
+
+        assert!(achievements.check(90, 4, Duration::ZERO).is_empty());
+        assert!(!achievements.is_triggered("First 100 points!"));
+
+        let triggered = achievements.check(110, 4, Duration::ZERO);
+        assert_eq!(triggered.len(), 1);
+        assert_eq!(triggered[0].name, "First 100 points!");
+        assert!(achievements.is_triggered("First 100 points!"));
+        assert_eq!(achievements.toast(), Some("First 100 points!"));
+
+        assert!(achievements.check(120, 4, Duration::ZERO).is_empty());
+    }
+}
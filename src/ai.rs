@@ -0,0 +1,131 @@
+use crate::game::{Direction, Game, Position};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+/// Picks the next direction for a computer-controlled snake.
+///
+/// Greedily takes the safe move that gets closest to the food, preferring to
+/// keep going straight on ties. A move is safe if `Game::head_after` allows
+/// it, so walls, the body, solid obstacles and spikes are all avoided. If
+/// every move is fatal the current direction is kept.
+pub fn autopilot_direction(game: &Game, board_width: u16, board_height: u16) -> Direction {
+    let current = game.snake().direction();
+
+    DIRECTIONS
+        .into_iter()
+        .filter(|&direction| direction != current.opposite())
+        .filter_map(|direction| Some((direction, game.head_after(direction)?)))
+        .min_by_key(|&(direction, next)| {
+            (
+                game.food().map_or(0, |food| {
+                    distance(game, next, food, board_width, board_height)
+                }),
+                direction != current,
+            )
+        })
+        .map_or(current, |(direction, _)| direction)
+}
+
+/// Steps from `from` to `to`, going round through an edge only where that
+/// edge wraps.
+fn distance(game: &Game, from: Position, to: Position, board_width: u16, board_height: u16) -> u16 {
+    let dx = axis_distance(
+        from.x,
+        to.x,
+        board_width,
+        game.wraps_towards(Direction::Left),
+        game.wraps_towards(Direction::Right),
+    );
+    let dy = axis_distance(
+        from.y,
+        to.y,
+        board_height,
+        game.wraps_towards(Direction::Up),
+        game.wraps_towards(Direction::Down),
+    );
+    dx + dy
+}
+
+fn axis_distance(from: u16, to: u16, size: u16, wraps_low: bool, wraps_high: bool) -> u16 {
+    let direct = from.abs_diff(to);
+    // The way round leaves through the edge on the far side from `to`
+    let wraps = if from < to { wraps_low } else { wraps_high };
+    if wraps && direct > 0 {
+        direct.min(size - direct)
+    } else {
+        direct
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+    use crate::game::WrapEdges;
+    use crate::scenario::Scenario;
+
+    #[test]
+    fn test_autopilot_avoids_walls() {
+        // Moving right towards the edge of a board with solid walls
+        let mut game = Game::new(12, 6);
+        while game.snake().head().x < 11 {
+            game.update();
+        }
+
+        let direction = autopilot_direction(&game, 12, 6);
+
+        assert_ne!(direction, Direction::Right);
+    }
+
+    #[test]
+    fn test_autopilot_respects_solid_edges_while_wrapping() {
+        // Only the sides wrap, so going up off the top is fatal even though
+        // the food is just across it
+        let config = GameConfig::new(12, 6)
+            .with_wall_wrapping(true)
+            .with_wrap_edges(WrapEdges {
+                top: false,
+                bottom: false,
+                left: true,
+                right: true,
+            })
+            .with_scenario(Scenario {
+                board_width: 12,
+                board_height: 6,
+                body: vec![Position::new(5, 0), Position::new(5, 1)],
+                direction: Direction::Up,
+                food: Some(Position::new(5, 4)),
+                score: 0,
+            });
+        let game = Game::from_config(&config);
+
+        assert_ne!(autopilot_direction(&game, 12, 6), Direction::Up);
+    }
+
+    #[test]
+    fn test_autopilot_avoids_obstacles_and_spikes() {
+        // Something deadly sits between the head and the food
+        let scenario = Scenario {
+            board_width: 12,
+            board_height: 6,
+            body: vec![Position::new(3, 2), Position::new(2, 2)],
+            direction: Direction::Right,
+            food: Some(Position::new(8, 2)),
+            score: 0,
+        };
+        let config = GameConfig::new(12, 6).with_scenario(scenario);
+        let blocker = vec![Position::new(4, 2)];
+
+        let game = Game::from_config(&config.clone().with_obstacles(blocker.clone()));
+        assert_eq!(game.food(), Some(Position::new(8, 2)));
+        assert_ne!(autopilot_direction(&game, 12, 6), Direction::Right);
+
+        let game = Game::from_config(&config.with_spikes(blocker));
+        assert_ne!(autopilot_direction(&game, 12, 6), Direction::Right);
+    }
+}
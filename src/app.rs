@@ -1,110 +1,505 @@
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture},
+    event::{DisableMouseCapture, EnableMouseCapture, Event},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{
     Terminal,
     backend::{Backend, CrosstermBackend},
+    layout::Rect,
 };
 use std::{
-    io,
-    time::{Duration, Instant},
+    fs, io,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use crate::{
-    config::GameConfig,
-    game::Game,
-    input::{InputAction, InputHandler},
-    renderer::TuiRenderer,
+    achievements::Achievements,
+    ai::autopilot_direction,
+    clock::{Clock, SystemClock},
+    config::{GameConfig, Theme},
+    death_replay::DeathReplay,
+    game::{Game, GameEvent, GameState},
+    input::{DirectionBuffer, InputAction, InputHandler},
+    power_up::SpeedEffect,
+    renderer::{BoardLayout, FrameContext, Renderer, TuiRenderer},
     sound::SoundSystem,
+    stamina::Stamina,
+    summary::GameSummary,
 };
 
-pub struct App<I: InputHandler, S: SoundSystem> {
+/// How long to wait for input while the game is paused or over. Nothing
+/// changes on screen then, so the loop can block instead of spinning.
+const IDLE_POLL_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long the name of a newly picked theme stays on screen.
+const THEME_TOAST_DURATION: Duration = Duration::from_secs(2);
+
+/// How long to wait for input before the loop must run again: until the next
+/// tick while playing, otherwise just blocking on input.
+fn poll_timeout(state: GameState, tick_rate: Duration, since_last_tick: Duration) -> Duration {
+    match state {
+        // The victory screen is animated, so it keeps ticking
+        GameState::Playing | GameState::Won => tick_rate.saturating_sub(since_last_tick),
+        GameState::Paused | GameState::GameOver => IDLE_POLL_TIMEOUT,
+    }
+}
+
+/// Whether to redraw this pass of the loop. Changes are drawn straight away;
+/// otherwise an unchanged screen is redrawn at most once per `frame_budget`.
+/// `None` for either timing always draws.
+fn should_draw(
+    changed: bool,
+    since_last_draw: Option<Duration>,
+    frame_budget: Option<Duration>,
+) -> bool {
+    match (since_last_draw, frame_budget) {
+        (Some(elapsed), Some(budget)) => changed || elapsed >= budget,
+        _ => true,
+    }
+}
+
+/// Whether the game has been left alone long enough to start the self-playing
+/// attract mode. `timeout` of `None` disables attract mode.
+fn should_enter_attract_mode(idle: Duration, timeout: Option<Duration>) -> bool {
+    timeout.is_some_and(|timeout| idle >= timeout)
+}
+
+/// Called with every event of the player's game and the game right after it.
+pub type EventCallback = Box<dyn FnMut(GameEvent, &Game)>;
+
+pub struct App<I: InputHandler, S: SoundSystem, R: Renderer = TuiRenderer, C: Clock = SystemClock> {
     game: Game,
     config: GameConfig,
-    renderer: TuiRenderer,
+    renderer: R,
     input_handler: I,
     sound_system: S,
+    event_callback: Option<EventCallback>,
+    achievements: Achievements,
+    speed_effect: SpeedEffect,
+    stamina: Stamina,
+    death_replay: DeathReplay,
+    theme: Theme,
+    /// Name of the theme just switched to and when to stop showing it.
+    theme_toast: Option<(String, Instant)>,
+    direction_buffer: DirectionBuffer,
+    turned_this_tick: bool,
+    time_survived: Duration,
+    attract_mode: bool,
+    win_frame: u32,
+    tick_count: u64,
+    frame_count: u64,
+    frame_area: Rect,
+    needs_redraw: bool,
+    clock: C,
+    last_draw: Option<Instant>,
+    last_tick: Instant,
+    last_input: Instant,
     should_quit: bool,
 }
 
-impl<I: InputHandler, S: SoundSystem> App<I, S> {
-    pub fn new(config: GameConfig, input_handler: I, sound_system: S) -> Self {
-        let mut game = Game::new(config.board_width, config.board_height);
-        game.set_wall_wrapping(config.wall_wrapping);
+impl<I: InputHandler, S: SoundSystem, R: Renderer> App<I, S, R> {
+    pub fn new(config: GameConfig, input_handler: I, sound_system: S, renderer: R) -> Self {
+        Self::with_clock(config, input_handler, sound_system, renderer, SystemClock)
+    }
+}
+
+impl<I: InputHandler, S: SoundSystem, R: Renderer, C: Clock> App<I, S, R, C> {
+    /// Like `new`, but timing the loop with `clock` instead of the system
+    /// clock.
+    pub fn with_clock(
+        config: GameConfig,
+        input_handler: I,
+        sound_system: S,
+        renderer: R,
+        clock: C,
+    ) -> Self {
         Self {
-            game,
+            game: Game::from_config(&config),
+            direction_buffer: DirectionBuffer::new(config.input_buffer),
             config,
-            renderer: TuiRenderer::new(),
+            renderer,
             input_handler,
             sound_system,
+            event_callback: None,
+            achievements: Achievements::default(),
+            speed_effect: SpeedEffect::new(),
+            stamina: Stamina::new(),
+            death_replay: DeathReplay::new(),
+            theme: Theme::default(),
+            theme_toast: None,
+            turned_this_tick: false,
+            time_survived: Duration::ZERO,
+            attract_mode: false,
+            win_frame: 0,
+            tick_count: 0,
+            frame_count: 0,
+            frame_area: Rect::default(),
+            needs_redraw: true,
+            last_draw: None,
+            last_tick: clock.now(),
+            last_input: clock.now(),
+            clock,
             should_quit: false,
         }
     }
 
+    /// Calls `callback` after every game update that produced an event, for
+    /// embedding apps that want to react to them. The attract mode demo
+    /// doesn't report events.
+    pub fn with_event_callback(mut self, callback: impl FnMut(GameEvent, &Game) + 'static) -> Self {
+        self.event_callback = Some(Box::new(callback));
+        self
+    }
+
     pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
-        let tick_rate = Duration::from_millis(100);
-        let mut last_tick = Instant::now();
-        let mut direction_store_next_tick = None;
+        self.run_loop(terminal, None)
+    }
 
-        loop {
-            terminal.draw(|f| {
-                self.renderer.draw_frame(f, &self.game, &self.config);
-            })?;
-
-            let timeout = tick_rate
-                .checked_sub(last_tick.elapsed())
-                .unwrap_or_else(|| Duration::from_secs(0));
-
-            if event::poll(timeout)? {
-                if let Ok(action) = self.input_handler.handle_input(event::read()?) {
-                    match action {
-                        InputAction::Move(direction) => {
-                            // Only allow one direction change per tick
-                            if direction_store_next_tick.is_none() {
-                                self.game.set_direction(direction);
-                            }
-                            direction_store_next_tick = Some(direction);
-                        }
-                        InputAction::Pause => {
-                            self.game.toggle_pause();
-                        }
-                        InputAction::Restart => {
-                            self.game.reset();
-                        }
-                        InputAction::Quit => {
-                            self.should_quit = true;
-                        }
-                        InputAction::None => {}
-                    }
-                }
+    /// Runs the app until the player quits or `max_ticks` game ticks have
+    /// passed, so scripted sessions always end.
+    pub fn run_ticks<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        max_ticks: u64,
+    ) -> io::Result<()> {
+        self.run_loop(terminal, Some(max_ticks))
+    }
+
+    /// Runs `max_ticks` game ticks back to back without drawing or waiting,
+    /// reading at most one input event before each tick. Stops early if the
+    /// player quits. Paired with `ScriptedInputHandler` this replays a whole
+    /// session deterministically, however fast the machine is.
+    pub fn run_headless(&mut self, max_ticks: u64) -> io::Result<()> {
+        for _ in 0..max_ticks {
+            if let Some(event) = self.input_handler.next_event(Duration::ZERO)? {
+                self.handle_event(event);
+            }
+            if self.should_quit {
+                break;
             }
 
-            if last_tick.elapsed() >= tick_rate {
-                let game_event = self.game.update();
-                self.sound_system.play_sound(game_event);
+            self.tick(self.current_tick_rate());
+            self.tick_count = self.tick_count.wrapping_add(1);
+        }
 
-                // Update high score if game over
-                if matches!(game_event, crate::game::GameEvent::GameOver) {
-                    self.config.update_high_score(self.game.score());
-                }
+        Ok(())
+    }
+
+    /// Game being played, e.g. to inspect it after a scripted run.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    fn run_loop<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        max_ticks: Option<u64>,
+    ) -> io::Result<()> {
+        let mut ticks = 0;
+        self.last_tick = self.clock.now();
+        self.last_input = self.clock.now();
 
-                last_tick = Instant::now();
-                self.game.set_direction(
-                    direction_store_next_tick
-                        .take()
-                        .unwrap_or(self.game.snake().direction()),
-                );
+        loop {
+            if self.step(terminal)? {
+                ticks += 1;
             }
 
-            if self.should_quit {
+            if self.should_quit || max_ticks.is_some_and(|max_ticks| ticks >= max_ticks) {
                 break;
             }
         }
 
         Ok(())
     }
+
+    /// One pass of the app loop: draw, wait for input until the next tick is
+    /// due and tick if it is. Returns whether the game ticked.
+    fn step<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<bool> {
+        let tick_rate = self.current_tick_rate();
+
+        let frame_budget = self.config.max_fps.map(|fps| Duration::from_secs(1) / fps);
+        let since_last_draw = self.last_draw.map(|last_draw| self.since(last_draw));
+        if should_draw(self.needs_redraw, since_last_draw, frame_budget) {
+            self.frame_area = self.draw(terminal, tick_rate)?;
+            self.last_draw = Some(self.clock.now());
+            self.needs_redraw = false;
+        }
+
+        // Let pointer input steer relative to where the head was drawn
+        let head_position =
+            BoardLayout::for_frame(&self.config, self.game.dimensions(), self.frame_area).map(
+                |layout| {
+                    let head = layout.cell_rect(self.game.snake().head());
+                    (head.x, head.y)
+                },
+            );
+        self.input_handler.set_head_position(head_position);
+
+        // The demo and the death replay are animated, so keep ticking even if
+        // it's game over
+        let state = if self.attract_mode || self.death_replay.is_playing() {
+            GameState::Playing
+        } else {
+            self.game.state()
+        };
+        let mut timeout = poll_timeout(state, tick_rate, self.since(self.last_tick));
+        // Wake up in time to take a toast down, even while idle
+        if let Some(until_expiry) = self.next_toast_expiry(tick_rate) {
+            timeout = timeout.min(until_expiry);
+        }
+
+        if let Some(event) = self.input_handler.next_event(timeout)? {
+            // Input may change the game, and resizes need a full redraw
+            self.needs_redraw = true;
+            self.handle_event(event);
+        }
+
+        // The theme toast runs on the clock, so it also clears while paused
+        let now = self.clock.now();
+        if self
+            .theme_toast
+            .as_ref()
+            .is_some_and(|&(_, shown_until)| now >= shown_until)
+        {
+            self.theme_toast = None;
+            self.needs_redraw = true;
+        }
+
+        if !self.attract_mode
+            && should_enter_attract_mode(self.since(self.last_input), self.config.attract_timeout)
+        {
+            self.attract_mode = true;
+            self.restart();
+        }
+
+        if self.since(self.last_tick) < tick_rate {
+            return Ok(false);
+        }
+        if self.attract_mode {
+            self.attract_tick();
+        } else {
+            self.tick(tick_rate);
+        }
+        self.last_tick = self.clock.now();
+        self.tick_count = self.tick_count.wrapping_add(1);
+        self.needs_redraw = true;
+        Ok(true)
+    }
+
+    /// Time until a toast on screen next needs updating: the theme toast's
+    /// deadline, or the next tick for achievement toasts, which count ticks.
+    fn next_toast_expiry(&self, tick_rate: Duration) -> Option<Duration> {
+        let theme = self
+            .theme_toast
+            .as_ref()
+            .map(|&(_, shown_until)| shown_until.saturating_duration_since(self.clock.now()));
+        let achievement = self
+            .achievements
+            .toast()
+            .map(|_| tick_rate.saturating_sub(self.since(self.last_tick)));
+        theme.into_iter().chain(achievement).min()
+    }
+
+    /// Tick interval after speed power-ups and sprinting.
+    fn current_tick_rate(&self) -> Duration {
+        self.stamina
+            .tick_rate(self.speed_effect.tick_rate(self.config.tick_rate()))
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        let Ok(action) = self.input_handler.handle_input(event) else {
+            return;
+        };
+        if action != InputAction::None {
+            self.last_input = self.clock.now();
+        }
+
+        if self.attract_mode && !matches!(action, InputAction::None | InputAction::Quit) {
+            // Any key ends the demo and hands a fresh game to the player
+            self.attract_mode = false;
+            self.restart();
+        } else {
+            self.handle_action(action);
+        }
+    }
+
+    /// Time passed since `instant` according to the app's clock.
+    fn since(&self, instant: Instant) -> Duration {
+        self.clock.now().saturating_duration_since(instant)
+    }
+
+    /// Draws the current state, returning the size of the frame drawn.
+    fn draw<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        tick_rate: Duration,
+    ) -> io::Result<Rect> {
+        let mut frame_area = Rect::default();
+        terminal.draw(|f| {
+            frame_area = f.area();
+            let context = FrameContext {
+                toast: self
+                    .theme_toast
+                    .as_ref()
+                    .map(|(name, _)| name.as_str())
+                    .or(self.achievements.toast()),
+                tick_rate,
+                win_frame: self.win_frame,
+                tick: self.tick_count,
+                frame: self.frame_count,
+                stamina: self.stamina.ratio(),
+                time_survived: self.time_survived,
+                replay_snake: self.death_replay.current(),
+            };
+            self.renderer.draw(f, &self.game, &self.config, &context);
+        })?;
+        self.frame_count = self.frame_count.wrapping_add(1);
+        Ok(frame_area)
+    }
+
+    /// Stats of the current game, for printing after the app exits.
+    pub fn summary(&self) -> GameSummary {
+        GameSummary::new(&self.game, &self.config, self.time_survived)
+    }
+
+    /// Routes `action` by game state: steering and toggling walls only while
+    /// playing, resuming only while paused, restarting only once the game is
+    /// over and quitting at any time.
+    fn handle_action(&mut self, action: InputAction) {
+        match (self.game.state(), action) {
+            (GameState::Playing, InputAction::Move(direction)) => {
+                // Only allow one direction change per tick, queue the rest
+                if self.turned_this_tick {
+                    self.direction_buffer.push(direction);
+                } else {
+                    self.game.set_direction(direction);
+                    self.turned_this_tick = true;
+                }
+            }
+            (GameState::Playing, InputAction::Boost) => {
+                self.stamina.toggle_boost();
+            }
+            (GameState::Playing | GameState::Paused, InputAction::Pause) => {
+                self.game.toggle_pause();
+            }
+            (GameState::Playing, InputAction::ToggleWalls) => {
+                // The config only sets the starting value
+                let wrapping = self.game.wall_wrapping();
+                self.game.set_wall_wrapping(!wrapping);
+            }
+            (_, InputAction::CycleTheme) => {
+                self.theme = self.theme.next();
+                self.config.apply_theme(self.theme);
+                let name = format!("{}: {}", self.config.messages.theme, self.theme.name());
+                self.theme_toast = Some((name, self.clock.now() + THEME_TOAST_DURATION));
+            }
+            (GameState::GameOver | GameState::Won, InputAction::Restart) => {
+                self.restart();
+            }
+            (_, InputAction::Snapshot) => {
+                // Like the high score file, a failed write shouldn't end the game
+                let _ = self.save_snapshot();
+            }
+            (_, InputAction::Quit) => {
+                self.should_quit = true;
+            }
+            // Anything else doesn't apply in the current state
+            _ => {}
+        }
+    }
+
+    /// Writes the board as text to `snake_snapshot_<unix time>.txt` in the
+    /// current directory.
+    fn save_snapshot(&self) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let path = format!("snake_snapshot_{}.txt", timestamp);
+        fs::write(path, self.game.to_text_grid())
+    }
+
+    fn restart(&mut self) {
+        self.game.reset();
+        self.achievements.reset();
+        self.direction_buffer.clear();
+        self.speed_effect.clear();
+        self.stamina.reset();
+        self.death_replay.clear();
+        self.time_survived = Duration::ZERO;
+        self.win_frame = 0;
+    }
+
+    fn tick(&mut self, tick_rate: Duration) {
+        // Drive the victory animation while the win screen is up
+        if self.game.state() == GameState::Won {
+            self.win_frame = self.win_frame.wrapping_add(1);
+        }
+
+        self.death_replay.tick();
+        let game_event = self.game.update();
+        if self.config.death_replay && game_event != GameEvent::None {
+            self.death_replay.record(self.game.snake().body());
+            if game_event == GameEvent::GameOver {
+                self.death_replay.start();
+            }
+        }
+        self.sound_system.play_sound(game_event);
+        if game_event != GameEvent::None {
+            if let Some(callback) = self.event_callback.as_mut() {
+                callback(game_event, &self.game);
+            }
+        }
+
+        self.achievements.tick();
+        if game_event != GameEvent::None {
+            self.time_survived += tick_rate;
+            self.achievements.check(
+                self.game.score(),
+                self.game.snake().len(),
+                self.time_survived,
+            );
+
+            // Count down the current speed effect before a new one can replace it
+            self.speed_effect.tick();
+            self.stamina.tick();
+        }
+        if let GameEvent::PowerUp(kind) = game_event {
+            self.speed_effect.activate(kind, self.config.power_up_ticks);
+        }
+
+        // Update personal bests if game over
+        if matches!(game_event, GameEvent::GameOver | GameEvent::Won) {
+            self.config.update_high_score(self.game.score());
+            self.config.update_longest_snake(self.game.snake().len());
+            self.config.update_best_survival(self.time_survived);
+            // A failed write shouldn't interrupt the game; the records are
+            // still kept in memory for this session
+            let _ = self.config.save_personal_bests();
+        }
+
+        self.turned_this_tick = false;
+        if let Some(direction) = self.direction_buffer.pop() {
+            self.game.set_direction(direction);
+            self.turned_this_tick = true;
+        }
+    }
+
+    /// Advances the self-playing demo. It makes no sound and doesn't count
+    /// towards scores or achievements.
+    fn attract_tick(&mut self) {
+        let direction = autopilot_direction(
+            &self.game,
+            self.config.board_width,
+            self.config.board_height,
+        );
+        self.game.set_direction(direction);
+
+        if matches!(self.game.update(), GameEvent::GameOver | GameEvent::Won) {
+            self.restart();
+        }
+    }
 }
 
 pub fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
@@ -121,3 +516,421 @@ pub fn restore_terminal() -> io::Result<()> {
     execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, MockClock};
+    use crate::game::{Direction, Position};
+    use crate::input::ScriptedInputHandler;
+    use crate::power_up::PowerUpKind;
+    use crate::sound::NoSoundSystem;
+    use ratatui::{Frame, backend::TestBackend, style::Color};
+    use std::{cell::RefCell, rc::Rc};
+
+    struct NoInput;
+
+    impl InputHandler for NoInput {
+        type Error = io::Error;
+
+        fn handle_input(&self, _event: Event) -> Result<InputAction, Self::Error> {
+            Ok(InputAction::None)
+        }
+    }
+
+    /// Counts frames instead of drawing them.
+    #[derive(Default)]
+    struct CountingRenderer {
+        frames: usize,
+    }
+
+    impl Renderer for CountingRenderer {
+        type Error = io::Error;
+
+        fn draw(
+            &mut self,
+            _f: &mut Frame,
+            _game: &Game,
+            _config: &GameConfig,
+            _context: &FrameContext,
+        ) {
+            self.frames += 1;
+        }
+
+        fn render(&mut self, _game: &Game, _config: &GameConfig) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn clear(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_scripted_pause_then_quit_exits_paused() {
+        let input = ScriptedInputHandler::new([InputAction::Pause, InputAction::Quit]);
+        let mut app = App::new(
+            GameConfig::new(20, 10),
+            input,
+            NoSoundSystem,
+            TuiRenderer::new(),
+        );
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+
+        app.run_ticks(&mut terminal, 50).unwrap();
+
+        assert!(app.should_quit);
+        assert_eq!(app.game().state(), GameState::Paused);
+        assert_eq!(app.input_handler.remaining(), 0);
+    }
+
+    #[test]
+    fn test_headless_run_replays_script_without_waiting() {
+        let config = GameConfig::new(12, 8).with_seed(17);
+        let script = [
+            InputAction::Move(Direction::Down),
+            InputAction::None,
+            InputAction::Move(Direction::Left),
+            InputAction::None,
+            InputAction::Move(Direction::Up),
+        ];
+        let mut app = App::new(
+            config.clone(),
+            ScriptedInputHandler::new(script),
+            NoSoundSystem,
+            CountingRenderer::default(),
+        );
+
+        // Far more ticks than real time would allow in a test
+        app.run_headless(40).unwrap();
+
+        let mut expected = Game::from_config(&config);
+        for tick in 0..40 {
+            if let Some(InputAction::Move(direction)) = script.get(tick) {
+                expected.set_direction(*direction);
+            }
+            expected.update();
+        }
+        assert_eq!(app.tick_count, 40);
+        assert_eq!(app.renderer.frames, 0);
+        assert_eq!(app.game().score(), expected.score());
+        assert_eq!(app.game().snake().head(), expected.snake().head());
+    }
+
+    #[test]
+    fn test_death_replay_rebuilds_heads_before_crash() {
+        let config = GameConfig::new(12, 8)
+            .with_wall_wrapping(false)
+            .with_seed(3)
+            .with_death_replay(true);
+        let mut app = App::new(
+            config.clone(),
+            ScriptedInputHandler::new([InputAction::Move(Direction::Up)]),
+            NoSoundSystem,
+            CountingRenderer::default(),
+        );
+        app.run_headless(30).unwrap();
+        assert_eq!(app.game().state(), GameState::GameOver);
+
+        let mut expected = Game::from_config(&config);
+        expected.set_direction(Direction::Up);
+        let mut heads = Vec::new();
+        while expected.state() == GameState::Playing {
+            expected.update();
+            heads.push(expected.snake().head());
+        }
+        let replayed: Vec<Position> = app.death_replay.frames().map(|body| body[0]).collect();
+        assert_eq!(replayed, heads[heads.len() - replayed.len()..]);
+        assert_eq!(app.game().score(), expected.score());
+
+        // A new game starts without the old replay
+        app.restart();
+        assert!(!app.death_replay.is_playing());
+    }
+
+    #[test]
+    fn test_cycle_theme_advances_and_recolors() {
+        let mut app = App::new(
+            GameConfig::new(20, 10),
+            NoInput,
+            NoSoundSystem,
+            CountingRenderer::default(),
+        );
+        assert_eq!(app.theme, Theme::Classic);
+
+        app.handle_action(InputAction::CycleTheme);
+        assert_eq!(app.theme, Theme::Ocean);
+        assert_eq!(app.config.snake_color, Color::Cyan);
+        assert_eq!(app.theme_toast.as_ref().unwrap().0, "Theme: Ocean");
+
+        app.handle_action(InputAction::CycleTheme);
+        app.handle_action(InputAction::CycleTheme);
+        assert_eq!(app.theme, Theme::Classic);
+        assert_eq!(app.config.snake_color, GameConfig::default().snake_color);
+
+        for theme in Theme::ALL {
+            assert!(
+                GameConfig::default()
+                    .with_theme(theme)
+                    .validate_colors()
+                    .is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn test_theme_toast_expires_while_paused() {
+        let clock = MockClock::new();
+        let mut app = App::with_clock(
+            GameConfig::new(20, 10),
+            ScriptedInputHandler::new([InputAction::Pause, InputAction::CycleTheme])
+                .with_clock(clock.clone()),
+            NoSoundSystem,
+            CountingRenderer::default(),
+            clock.clone(),
+        );
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+
+        app.step(&mut terminal).unwrap();
+        app.step(&mut terminal).unwrap();
+        assert_eq!(app.game().state(), GameState::Paused);
+        assert!(app.theme_toast.is_some());
+
+        clock.advance(THEME_TOAST_DURATION);
+        app.step(&mut terminal).unwrap();
+        assert!(app.theme_toast.is_none());
+    }
+
+    #[test]
+    fn test_idle_wait_stops_at_next_toast_expiry() {
+        let clock = MockClock::new();
+        let config = GameConfig::new(20, 10);
+        let tick_rate = config.tick_rate();
+        let mut app = App::with_clock(
+            config,
+            ScriptedInputHandler::new([InputAction::Pause, InputAction::CycleTheme])
+                .with_clock(clock.clone()),
+            NoSoundSystem,
+            CountingRenderer::default(),
+            clock.clone(),
+        );
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        app.step(&mut terminal).unwrap();
+        app.step(&mut terminal).unwrap();
+
+        // Paused with nothing to show, the loop would block for a second
+        clock.advance(THEME_TOAST_DURATION - Duration::from_millis(300));
+        let before = clock.now();
+        app.step(&mut terminal).unwrap();
+        assert_eq!(clock.now() - before, Duration::from_millis(300));
+        app.step(&mut terminal).unwrap();
+        assert!(app.theme_toast.is_none());
+
+        // Achievement toasts count ticks, so the wait ends at the next tick
+        app.achievements.check(100, 0, Duration::ZERO);
+        let before = clock.now();
+        app.step(&mut terminal).unwrap();
+        assert!(clock.now() - before <= tick_rate);
+    }
+
+    #[test]
+    fn test_mock_clock_drives_one_update_per_tick() {
+        let clock = MockClock::new();
+        let config = GameConfig::new(20, 10);
+        let tick_rate = config.tick_rate();
+        let mut app = App::with_clock(
+            config,
+            ScriptedInputHandler::new([]).with_clock(clock.clone()),
+            NoSoundSystem,
+            CountingRenderer::default(),
+            clock.clone(),
+        );
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let start = app.game().snake().head();
+        let started = clock.now();
+
+        // Each step waits out the rest of the tick on the mock clock
+        assert!(app.step(&mut terminal).unwrap());
+        assert_eq!(clock.now() - started, tick_rate);
+
+        clock.advance(tick_rate / 2);
+        assert!(app.step(&mut terminal).unwrap());
+        assert_eq!(clock.now() - started, tick_rate * 2);
+
+        assert_eq!(app.game().snake().head().x, start.x + 2);
+    }
+
+    #[test]
+    fn test_app_draws_through_injected_renderer() {
+        let mut app = App::new(
+            GameConfig::new(20, 10),
+            NoInput,
+            NoSoundSystem,
+            CountingRenderer::default(),
+        );
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+
+        app.draw(&mut terminal, Duration::from_millis(100)).unwrap();
+        app.draw(&mut terminal, Duration::from_millis(100)).unwrap();
+
+        assert_eq!(app.renderer.frames, 2);
+        assert_eq!(app.frame_count, 2);
+    }
+
+    #[test]
+    fn test_toggle_walls_applies_on_next_tick() {
+        let config = GameConfig::new(20, 10)
+            .with_wall_wrapping(false)
+            .with_seed(6);
+        let mut app = App::new(config, NoInput, NoSoundSystem, CountingRenderer::default());
+        // The head starts a few cells from the right wall
+        while app.game().snake().head().x < 19 {
+            app.game.update();
+        }
+
+        app.handle_action(InputAction::ToggleWalls);
+        assert!(app.game().wall_wrapping());
+        assert_eq!(app.game.update(), GameEvent::Wrapped);
+
+        app.handle_action(InputAction::ToggleWalls);
+        assert!(!app.game().wall_wrapping());
+        while app.game().snake().head().x < 19 {
+            app.game.update();
+        }
+        assert_eq!(app.game.update(), GameEvent::GameOver);
+    }
+
+    #[test]
+    fn test_event_callback_sees_food_then_game_over() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let recorded = Rc::clone(&events);
+        let config = GameConfig::new(12, 8)
+            .with_wall_wrapping(false)
+            .with_seed(9);
+
+        // Script the moves to the first food, then run straight into a wall
+        let mut planned = Game::from_config(&config);
+        let mut script = Vec::new();
+        while planned.food_eaten() == 0 {
+            let direction = autopilot_direction(&planned, 12, 8);
+            script.push(InputAction::Move(direction));
+            planned.set_direction(direction);
+            planned.update();
+        }
+        let mut app = App::new(
+            config,
+            ScriptedInputHandler::new(script),
+            NoSoundSystem,
+            CountingRenderer::default(),
+        )
+        .with_event_callback(move |event, _game| recorded.borrow_mut().push(event));
+
+        app.run_headless(200).unwrap();
+
+        let events = events.borrow();
+        assert!(!events.contains(&GameEvent::None));
+        let eaten = events
+            .iter()
+            .position(|event| matches!(event, GameEvent::FoodEaten { .. }))
+            .unwrap();
+        assert!(eaten < events.len() - 1);
+        assert_eq!(events.last(), Some(&GameEvent::GameOver));
+    }
+
+    #[test]
+    fn test_power_up_changes_app_tick_rate() {
+        let config = GameConfig::new(20, 10).with_seed(5).with_power_up_ticks(3);
+        let base = config.tick_rate();
+        let mut app = App::new(config, NoInput, NoSoundSystem, CountingRenderer::default());
+        let head = app.game().snake().head();
+        let ahead = Position::new(head.x + 1, head.y);
+        assert_ne!(app.game().food(), Some(ahead));
+        app.game.place_power_up(ahead, PowerUpKind::Slow);
+
+        app.tick(base);
+        assert_eq!(app.current_tick_rate(), base * 2);
+
+        for _ in 0..3 {
+            app.tick(app.current_tick_rate());
+        }
+        assert_eq!(app.current_tick_rate(), base);
+    }
+
+    #[test]
+    fn test_pause_ignores_wall_toggle_and_restart() {
+        let config = GameConfig::new(20, 10).with_seed(2);
+        let mut app = App::new(config, NoInput, NoSoundSystem, CountingRenderer::default());
+        app.game.update();
+        let head = app.game().snake().head();
+
+        app.handle_action(InputAction::Pause);
+        app.handle_action(InputAction::ToggleWalls);
+        app.handle_action(InputAction::Restart);
+
+        assert_eq!(app.game().state(), GameState::Paused);
+        assert!(app.game().wall_wrapping());
+        assert_eq!(app.game().snake().head(), head);
+
+        // Restarting is for once the game is over
+        app.handle_action(InputAction::Pause);
+        app.game.set_wall_wrapping(false);
+        while app.game().state() == GameState::Playing {
+            app.game.update();
+        }
+        app.handle_action(InputAction::Restart);
+        assert_eq!(app.game().state(), GameState::Playing);
+    }
+
+    #[test]
+    fn test_move_ignored_after_game_over() {
+        let config = GameConfig::new(20, 10).with_wall_wrapping(false);
+        let mut app = App::new(config, NoInput, NoSoundSystem, CountingRenderer::default());
+        while app.game().state() == GameState::Playing {
+            app.game.update();
+        }
+
+        app.handle_action(InputAction::Move(Direction::Up));
+
+        assert_eq!(app.game().snake().direction(), Direction::Right);
+        assert!(!app.turned_this_tick);
+    }
+
+    #[test]
+    fn test_should_draw_within_frame_budget() {
+        let budget = Some(Duration::from_millis(50));
+
+        assert!(!should_draw(false, Some(Duration::from_millis(20)), budget));
+        assert!(should_draw(true, Some(Duration::from_millis(20)), budget));
+        assert!(should_draw(false, Some(Duration::from_millis(50)), budget));
+        // Nothing drawn yet, or no cap at all
+        assert!(should_draw(false, None, budget));
+        assert!(should_draw(false, Some(Duration::ZERO), None));
+    }
+
+    #[test]
+    fn test_attract_mode_after_idle_timeout() {
+        let timeout = Some(Duration::from_secs(30));
+
+        assert!(!should_enter_attract_mode(Duration::from_secs(29), timeout));
+        assert!(should_enter_attract_mode(Duration::from_secs(30), timeout));
+        assert!(should_enter_attract_mode(Duration::from_secs(90), timeout));
+        assert!(!should_enter_attract_mode(Duration::from_secs(90), None));
+    }
+
+    #[test]
+    fn test_poll_timeout_depends_on_state() {
+        let tick_rate = Duration::from_millis(100);
+        let elapsed = Duration::from_millis(30);
+
+        assert_eq!(
+            poll_timeout(GameState::Playing, tick_rate, elapsed),
+            Duration::from_millis(70)
+        );
+        assert_eq!(
+            poll_timeout(GameState::Playing, tick_rate, Duration::from_millis(150)),
+            Duration::ZERO
+        );
+        assert!(poll_timeout(GameState::Paused, tick_rate, elapsed) > tick_rate);
+        assert!(poll_timeout(GameState::GameOver, tick_rate, elapsed) > tick_rate);
+    }
+}
@@ -1,18 +1,255 @@
-use ratatui::style::Color;
+use ratatui::{style::Color, widgets::BorderType};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-#[derive(Clone, Debug)]
+use crate::daily::Date;
+use crate::game::{BoardShape, Direction, GOLDEN_FOOD_CHANCE, Position, WrapEdges};
+use crate::high_scores::{PersonalBests, load_personal_bests, save_personal_bests};
+use crate::input::KeyMap;
+use crate::messages::Messages;
+use crate::scenario::Scenario;
+
+/// Fastest allowed tick interval in milliseconds.
+pub const MIN_TICK_MS: u64 = 20;
+/// Slowest allowed tick interval in milliseconds.
+pub const MAX_TICK_MS: u64 = 1000;
+/// Highest level accepted by [`GameConfig::with_speed`].
+pub const MAX_SPEED: u8 = 10;
+
+/// Lowest luminance contrast ratio between two colors that are drawn on top
+/// of each other before they're considered hard to tell apart.
+const MIN_CONTRAST_RATIO: f64 = 1.5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// Two colors are too similar to tell apart on screen.
+    LowContrast {
+        first: &'static str,
+        second: &'static str,
+        color: Color,
+        other: Color,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::LowContrast {
+                first,
+                second,
+                color,
+                other,
+            } => write!(
+                f,
+                "{} color {} is too close to {} color {}",
+                first, color, second, other
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Where the board sits in the space left for it when it doesn't fill it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Offset into a space `free_width` by `free_height` larger than the
+    /// board that puts the board at this anchor.
+    pub fn offset(&self, free_width: u16, free_height: u16) -> (u16, u16) {
+        let x = match self {
+            Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft => 0,
+            Anchor::Top | Anchor::Center | Anchor::Bottom => free_width / 2,
+            Anchor::TopRight | Anchor::Right | Anchor::BottomRight => free_width,
+        };
+        let y = match self {
+            Anchor::TopLeft | Anchor::Top | Anchor::TopRight => 0,
+            Anchor::Left | Anchor::Center | Anchor::Right => free_height / 2,
+            Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight => free_height,
+        };
+        (x, y)
+    }
+}
+
+/// Digits a zero-padded score is filled out to.
+const SCORE_PAD_WIDTH: usize = 6;
+
+/// How scores are written out on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScoreFormat {
+    /// `1230`
+    #[default]
+    Plain,
+    /// `1,230`
+    Thousands,
+    /// `001230`
+    ZeroPadded,
+}
+
+impl ScoreFormat {
+    pub fn format(&self, score: u32) -> String {
+        match self {
+            ScoreFormat::Plain => score.to_string(),
+            ScoreFormat::Thousands => {
+                let digits = score.to_string();
+                let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+                for (i, digit) in digits.chars().enumerate() {
+                    if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                        formatted.push(',');
+                    }
+                    formatted.push(digit);
+                }
+                formatted
+            }
+            ScoreFormat::ZeroPadded => format!("{score:0SCORE_PAD_WIDTH$}"),
+        }
+    }
+}
+
+/// Named set of board colors that can be switched between while playing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    #[default]
+    Classic,
+    Ocean,
+    Ember,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Classic, Theme::Ocean, Theme::Ember];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Classic => "Classic",
+            Theme::Ocean => "Ocean",
+            Theme::Ember => "Ember",
+        }
+    }
+
+    /// The theme after this one, wrapping back to the first.
+    pub fn next(&self) -> Theme {
+        let index = Theme::ALL
+            .iter()
+            .position(|theme| theme == self)
+            .unwrap_or(0);
+        Theme::ALL[(index + 1) % Theme::ALL.len()]
+    }
+
+    /// Snake, food, wall and border colors, in that order.
+    fn colors(&self) -> [Color; 4] {
+        match self {
+            Theme::Classic => [Color::Green, Color::Red, Color::Gray, Color::White],
+            Theme::Ocean => [Color::Cyan, Color::LightRed, Color::Blue, Color::LightBlue],
+            Theme::Ember => [
+                Color::Yellow,
+                Color::LightMagenta,
+                Color::Red,
+                Color::LightYellow,
+            ],
+        }
+    }
+}
+
+/// All game settings. Saved profiles store everything except the personal
+/// bests and the daily challenge date, which are filled in at startup.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GameConfig {
     pub board_width: u16,
     pub board_height: u16,
     pub enable_sound: bool,
     pub enable_colors: bool,
     pub wall_wrapping: bool,
+    pub wrap_after_score: Option<u32>,
+    pub wrap_edges: WrapEdges,
+    pub wall_grace_ticks: u32,
     pub snake_color: Color,
     pub food_color: Color,
     pub wall_color: Color,
     pub background_color: Color,
     pub border_color: Color,
+    #[serde(skip)]
     pub high_score: u32,
+    #[serde(skip)]
+    pub longest_snake: usize,
+    #[serde(skip)]
+    pub best_survival: Duration,
+    #[serde(skip)]
+    pub high_score_file: Option<PathBuf>,
+    pub seed: Option<u64>,
+    #[serde(skip)]
+    pub daily_challenge: Option<Date>,
+    pub input_buffer: usize,
+    pub peaceful: bool,
+    pub power_up_ticks: u32,
+    pub mouse_control: bool,
+    pub title: String,
+    #[serde(with = "border_type_name")]
+    pub border_type: BorderType,
+    pub game_border: bool,
+    pub attract_timeout: Option<Duration>,
+    pub show_grid: bool,
+    pub eat_flash: bool,
+    pub board_shape: BoardShape,
+    pub board_anchor: Anchor,
+    pub score_format: ScoreFormat,
+    pub hunger_rate: Option<u32>,
+    pub combo_window: u32,
+    pub combo_multiplier: u32,
+    pub base_tick_ms: u64,
+    pub messages: Messages,
+    pub key_map: KeyMap,
+    pub self_collision: bool,
+    pub neck_grace: bool,
+    pub food_hint: bool,
+    pub min_cell_size: u16,
+    pub max_cell_size: Option<u16>,
+    pub golden_food_chance: f64,
+    pub food_avoids_next_head: bool,
+    pub pause_dim: bool,
+    pub keep_score_on_restart: bool,
+    pub food_pulse: bool,
+    pub food_pulse_ticks: u32,
+    pub connected_body: bool,
+    pub respawn_delay: u32,
+    pub respawn_on_death: bool,
+    pub start_paused: bool,
+    pub wait_for_input: bool,
+    pub show_progress: bool,
+    pub combo_meter: bool,
+    pub initial_direction: Direction,
+    pub ghost_preview: bool,
+    pub shrink_interval: u32,
+    pub lives: u32,
+    pub max_fps: Option<u32>,
+    pub minimap: bool,
+    pub hud_minimal: bool,
+    pub pause_stats: bool,
+    pub max_length: Option<usize>,
+    pub rainbow: bool,
+    pub death_replay: bool,
+    pub obstacles: Vec<Position>,
+    pub solid_obstacles: bool,
+    pub spikes: Vec<Position>,
+    pub spike_blink: bool,
+    #[serde(skip)]
+    pub scenario: Option<Scenario>,
 }
 
 impl Default for GameConfig {
@@ -23,12 +260,73 @@ impl Default for GameConfig {
             enable_sound: true,
             enable_colors: true,
             wall_wrapping: true,
+            wrap_after_score: None,
+            wrap_edges: WrapEdges::ALL,
+            wall_grace_ticks: 0,
             snake_color: Color::Green,
             food_color: Color::Red,
             wall_color: Color::Gray,
             background_color: Color::Black,
             border_color: Color::White,
             high_score: 0,
+            longest_snake: 0,
+            best_survival: Duration::ZERO,
+            high_score_file: None,
+            seed: None,
+            daily_challenge: None,
+            input_buffer: 1,
+            peaceful: false,
+            power_up_ticks: 50,
+            mouse_control: false,
+            title: String::from("Snake Game"),
+            border_type: BorderType::Plain,
+            game_border: true,
+            attract_timeout: None,
+            show_grid: false,
+            eat_flash: false,
+            board_shape: BoardShape::Rectangle,
+            board_anchor: Anchor::Center,
+            score_format: ScoreFormat::Plain,
+            hunger_rate: None,
+            combo_window: 0,
+            combo_multiplier: 2,
+            base_tick_ms: 100,
+            messages: Messages::default(),
+            key_map: KeyMap::default(),
+            self_collision: true,
+            neck_grace: false,
+            food_hint: false,
+            min_cell_size: 1,
+            max_cell_size: None,
+            golden_food_chance: GOLDEN_FOOD_CHANCE,
+            food_avoids_next_head: false,
+            pause_dim: true,
+            keep_score_on_restart: false,
+            food_pulse: false,
+            food_pulse_ticks: 4,
+            connected_body: false,
+            respawn_delay: 0,
+            respawn_on_death: false,
+            start_paused: false,
+            wait_for_input: false,
+            show_progress: false,
+            combo_meter: false,
+            initial_direction: Direction::Right,
+            ghost_preview: false,
+            shrink_interval: 0,
+            lives: 1,
+            max_fps: None,
+            minimap: false,
+            hud_minimal: false,
+            pause_stats: false,
+            max_length: None,
+            rainbow: false,
+            death_replay: false,
+            obstacles: Vec::new(),
+            solid_obstacles: true,
+            spikes: Vec::new(),
+            spike_blink: false,
+            scenario: None,
         }
     }
 }
@@ -57,6 +355,382 @@ impl GameConfig {
         self
     }
 
+    /// Starts with solid walls that turn into wrapping ones once the score
+    /// reaches `threshold`. `None` keeps the wall wrapping setting.
+    pub fn with_wrap_after_score(mut self, threshold: Option<u32>) -> Self {
+        self.wrap_after_score = threshold;
+        self
+    }
+
+    /// Edges that wrap while wrapping is on; the rest stay solid walls.
+    pub fn with_wrap_edges(mut self, edges: WrapEdges) -> Self {
+        self.wrap_edges = edges;
+        self
+    }
+
+    /// Walls wrap for the first `ticks` ticks of each snake before turning
+    /// solid, so starting next to one isn't an instant death.
+    pub fn with_wall_grace_ticks(mut self, ticks: u32) -> Self {
+        self.wall_grace_ticks = ticks;
+        self
+    }
+
+    /// Peaceful mode: collisions never end the game and walls always wrap.
+    pub fn with_peaceful(mut self, enable: bool) -> Self {
+        self.peaceful = enable;
+        self
+    }
+
+    /// Classic rules end the game when the snake runs into itself. Disabling
+    /// this lets the snake overlap its body for practice.
+    pub fn with_self_collision(mut self, enable: bool) -> Self {
+        self.self_collision = enable;
+        self
+    }
+
+    /// Forgives moves that double back onto the segment right behind the
+    /// head. Off by default, as in classic snake.
+    pub fn with_neck_grace(mut self, enable: bool) -> Self {
+        self.neck_grace = enable;
+        self
+    }
+
+    /// Chance from 0.0 to 1.0 that a newly spawned food is golden and worth
+    /// extra points. 0.0 disables golden food.
+    pub fn with_golden_food_chance(mut self, chance: f64) -> Self {
+        self.golden_food_chance = chance.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Never spawns food in the cell the head enters next tick, unless it's
+    /// the only free cell.
+    pub fn with_food_avoids_next_head(mut self, enable: bool) -> Self {
+        self.food_avoids_next_head = enable;
+        self
+    }
+
+    /// Keeps adding to the same score across restarts instead of starting
+    /// over from zero.
+    pub fn with_keep_score_on_restart(mut self, enable: bool) -> Self {
+        self.keep_score_on_restart = enable;
+        self
+    }
+
+    /// Number of ticks a slow or fast power-up stays in effect.
+    pub fn with_power_up_ticks(mut self, ticks: u32) -> Self {
+        self.power_up_ticks = ticks;
+        self
+    }
+
+    /// Lets clicking relative to the snake head steer the snake.
+    pub fn with_mouse_control(mut self, enable: bool) -> Self {
+        self.mouse_control = enable;
+        self
+    }
+
+    /// Title of the game area. An empty title draws the border only.
+    pub fn with_title(mut self, title: String) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Border style of the game, stats and controls panels.
+    pub fn with_border_type(mut self, border_type: BorderType) -> Self {
+        self.border_type = border_type;
+        self
+    }
+
+    /// Draws a border around the board inside the game panel. Turning it off
+    /// frees two rows and columns for the board on small terminals.
+    pub fn with_game_border(mut self, enable: bool) -> Self {
+        self.game_border = enable;
+        self
+    }
+
+    /// Starts a self-playing demo after `timeout` without input. The next
+    /// key press ends the demo with a fresh game. `None` disables it.
+    pub fn with_attract_mode(mut self, timeout: Option<Duration>) -> Self {
+        self.attract_timeout = timeout;
+        self
+    }
+
+    /// Smallest cell size, in rows, to draw the board with. If the terminal
+    /// is too small for it, the board is drawn as a minimap instead.
+    pub fn with_min_cell_size(mut self, size: u16) -> Self {
+        self.min_cell_size = size.max(1);
+        self
+    }
+
+    /// Largest cell size, in rows, so small boards don't blow up to fill a
+    /// big terminal.
+    pub fn with_max_cell_size(mut self, size: u16) -> Self {
+        self.max_cell_size = Some(size.max(1));
+        self
+    }
+
+    /// Always draws the board scaled down to a minimap, as happens anyway
+    /// when the full board doesn't fit the terminal.
+    pub fn with_minimap(mut self, enable: bool) -> Self {
+        self.minimap = enable;
+        self
+    }
+
+    /// Replaces the stats and controls panels with one status line below
+    /// the board, leaving more room for the board itself.
+    pub fn with_hud_minimal(mut self, enable: bool) -> Self {
+        self.hud_minimal = enable;
+        self
+    }
+
+    /// Lists the score, length and time played in the pause popup.
+    pub fn with_pause_stats(mut self, enable: bool) -> Self {
+        self.pause_stats = enable;
+        self
+    }
+
+    /// Draws a faint dot in every empty cell of the board.
+    pub fn with_grid(mut self, enable: bool) -> Self {
+        self.show_grid = enable;
+        self
+    }
+
+    /// Beginner assist: shows an arrow towards the food and its distance in
+    /// the stats panel.
+    pub fn with_food_hint(mut self, enable: bool) -> Self {
+        self.food_hint = enable;
+        self
+    }
+
+    /// Makes the food glyph pulse between a filled and a hollow shape.
+    /// Purely cosmetic.
+    pub fn with_food_pulse(mut self, enable: bool) -> Self {
+        self.food_pulse = enable;
+        self
+    }
+
+    /// Ticks each phase of the food pulse lasts.
+    pub fn with_food_pulse_interval(mut self, ticks: u32) -> Self {
+        self.food_pulse_ticks = ticks.max(1);
+        self
+    }
+
+    /// Colors the snake with a rainbow that moves along the body from frame
+    /// to frame. Needs colors enabled.
+    pub fn with_rainbow(mut self, enable: bool) -> Self {
+        self.rainbow = enable;
+        self
+    }
+
+    /// Replays the last few ticks in slow motion after a crash, before the
+    /// game over popup.
+    pub fn with_death_replay(mut self, enable: bool) -> Self {
+        self.death_replay = enable;
+        self
+    }
+
+    /// Draws the body as a continuous line with box-drawing characters
+    /// instead of separate circles.
+    pub fn with_connected_body(mut self, enable: bool) -> Self {
+        self.connected_body = enable;
+        self
+    }
+
+    /// Ticks the board stays empty after food is eaten before the next
+    /// food appears.
+    pub fn with_respawn_delay(mut self, ticks: u32) -> Self {
+        self.respawn_delay = ticks;
+        self
+    }
+
+    /// Teleports the snake to a free cell as a single segment when it
+    /// crashes, for a few points, instead of ending the game.
+    pub fn with_respawn_on_death(mut self, enable: bool) -> Self {
+        self.respawn_on_death = enable;
+        self
+    }
+
+    /// Lets the snake respawn after a crash until `lives` run out. 1 (the
+    /// default) means the first crash ends the game.
+    pub fn with_lives(mut self, lives: u32) -> Self {
+        self.lives = lives.max(1);
+        self
+    }
+
+    /// Redraws at most `fps` times a second between ticks. A game change is
+    /// always drawn straight away.
+    pub fn with_max_fps(mut self, fps: u32) -> Self {
+        self.max_fps = Some(fps.max(1));
+        self
+    }
+
+    /// Length the snake stops growing at while food keeps scoring. `None`
+    /// (the default) lets it grow until it fills the board.
+    pub fn with_max_length(mut self, max_length: Option<usize>) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Cells food never spawns on, as used by puzzle maps.
+    pub fn with_obstacles(mut self, obstacles: Vec<Position>) -> Self {
+        self.obstacles = obstacles;
+        self
+    }
+
+    /// Whether running into an obstacle ends the game, even while the edges
+    /// wrap (the default), or the snake may pass over it.
+    pub fn with_solid_obstacles(mut self, solid: bool) -> Self {
+        self.solid_obstacles = solid;
+        self
+    }
+
+    /// Deadly cells, fatal even while the edges wrap. Food may still spawn
+    /// right next to them.
+    pub fn with_spikes(mut self, spikes: Vec<Position>) -> Self {
+        self.spikes = spikes;
+        self
+    }
+
+    /// Makes the spikes blink. Purely cosmetic.
+    pub fn with_spike_blink(mut self, enable: bool) -> Self {
+        self.spike_blink = enable;
+        self
+    }
+
+    /// Walls off the outermost ring of the board every `ticks` ticks. 0
+    /// disables shrinking.
+    pub fn with_shrink_interval(mut self, ticks: u32) -> Self {
+        self.shrink_interval = ticks;
+        self
+    }
+
+    /// Marks the cell the head moves into next tick, unless that move is
+    /// fatal. Purely cosmetic.
+    pub fn with_ghost_preview(mut self, enable: bool) -> Self {
+        self.ghost_preview = enable;
+        self
+    }
+
+    /// Direction the snake faces at the start of every game.
+    pub fn with_initial_direction(mut self, direction: Direction) -> Self {
+        self.initial_direction = direction;
+        self
+    }
+
+    /// Shows a bar in the stats panel with the snake's progress towards
+    /// filling the board.
+    pub fn with_progress_bar(mut self, enable: bool) -> Self {
+        self.show_progress = enable;
+        self
+    }
+
+    /// Holds the snake still at the start of every game until the first
+    /// movement key.
+    pub fn with_wait_for_input(mut self, enable: bool) -> Self {
+        self.wait_for_input = enable;
+        self
+    }
+
+    /// Starts every game from `scenario` instead of the usual snake, on a
+    /// board of the scenario's size. The scenario should be validated, as
+    /// `Scenario::load` does.
+    pub fn with_scenario(mut self, scenario: Scenario) -> Self {
+        self.board_width = scenario.board_width;
+        self.board_height = scenario.board_height;
+        self.scenario = Some(scenario);
+        self
+    }
+
+    /// Starts the first game paused, waiting for the player to resume.
+    pub fn with_start_paused(mut self, enable: bool) -> Self {
+        self.start_paused = enable;
+        self
+    }
+
+    /// Dims the board behind the pause popup.
+    pub fn with_pause_dim(mut self, enable: bool) -> Self {
+        self.pause_dim = enable;
+        self
+    }
+
+    /// Flashes a burst where food was eaten for one frame.
+    pub fn with_eat_flash(mut self, enable: bool) -> Self {
+        self.eat_flash = enable;
+        self
+    }
+
+    /// Shape of the playfield. Cells outside the shape are walls, and
+    /// non-rectangular boards never wrap.
+    pub fn with_board_shape(mut self, shape: BoardShape) -> Self {
+        self.board_shape = shape;
+        self
+    }
+
+    /// Pins the board to a side or corner of its panel instead of centering
+    /// it, so it stays put when the terminal is resized.
+    pub fn with_board_anchor(mut self, anchor: Anchor) -> Self {
+        self.board_anchor = anchor;
+        self
+    }
+
+    /// How the score and best score are written out.
+    pub fn with_score_format(mut self, format: ScoreFormat) -> Self {
+        self.score_format = format;
+        self
+    }
+
+    /// Drains one point every `rate` ticks without eating, never going
+    /// below zero. Rewards heading straight for the food.
+    pub fn with_hunger(mut self, rate: u32) -> Self {
+        self.hunger_rate = Some(rate);
+        self
+    }
+
+    /// Multiplies the points of food eaten within `window` ticks of the
+    /// previous meal by `multiplier`. A `window` of 0 disables combos.
+    pub fn with_combo(mut self, window: u32, multiplier: u32) -> Self {
+        self.combo_window = window;
+        self.combo_multiplier = multiplier.max(1);
+        self
+    }
+
+    /// Shows a bar in the stats panel that fills after each meal and drains
+    /// as the combo window closes. Only drawn with combos enabled.
+    pub fn with_combo_meter(mut self, enable: bool) -> Self {
+        self.combo_meter = enable;
+        self
+    }
+
+    /// Milliseconds between game ticks before power-ups or sprinting change
+    /// the pace. Clamped to `MIN_TICK_MS..=MAX_TICK_MS`.
+    pub fn with_tick_ms(mut self, ms: u64) -> Self {
+        self.base_tick_ms = ms.clamp(MIN_TICK_MS, MAX_TICK_MS);
+        self
+    }
+
+    /// Sets the tick interval from a speed level between 1 (slow, 230ms)
+    /// and `MAX_SPEED` (fast, 50ms). Out of range levels are clamped.
+    pub fn with_speed(self, level: u8) -> Self {
+        let level = u64::from(level.clamp(1, MAX_SPEED));
+        self.with_tick_ms(250 - level * 20)
+    }
+
+    /// Base interval between game ticks.
+    pub fn tick_rate(&self) -> Duration {
+        Duration::from_millis(self.base_tick_ms)
+    }
+
+    /// UI text shown by the renderer, for translations.
+    pub fn with_messages(mut self, messages: Messages) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Key bindings, also used for the hints in the controls panel.
+    pub fn with_key_map(mut self, key_map: KeyMap) -> Self {
+        self.key_map = key_map;
+        self
+    }
+
     pub fn with_snake_color(mut self, color: Color) -> Self {
         self.snake_color = color;
         self
@@ -82,9 +756,586 @@ impl GameConfig {
         self
     }
 
+    /// Sets the snake, food, wall and border colors from `theme`. The
+    /// background is left alone.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.apply_theme(theme);
+        self
+    }
+
+    /// Like `with_theme`, for switching themes on a config in use.
+    pub fn apply_theme(&mut self, theme: Theme) {
+        [
+            self.snake_color,
+            self.food_color,
+            self.wall_color,
+            self.border_color,
+        ] = theme.colors();
+    }
+
+    /// Number of direction changes that can be queued for upcoming ticks
+    /// after the one applied immediately. 0 keeps only in-tick changes, 2-3
+    /// is more forgiving for quick turns but larger buffers can feel "laggy"
+    /// since queued turns play out several ticks after the key press.
+    pub fn with_input_buffer(mut self, capacity: usize) -> Self {
+        self.input_buffer = capacity;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Plays the shared challenge for `date`, seeding food placement from it.
+    pub fn with_daily_challenge(mut self, date: Date) -> Self {
+        self.seed = Some(date.seed());
+        self.daily_challenge = Some(date);
+        self
+    }
+
+    /// Persists personal bests to `path` and loads the records for the
+    /// current board size. Set the board size before calling this.
+    pub fn with_high_score_file(mut self, path: PathBuf) -> Self {
+        let bests = load_personal_bests(&path)
+            .get(&(self.board_width, self.board_height))
+            .copied()
+            .unwrap_or_default();
+        self.high_score = bests.score;
+        self.longest_snake = bests.length;
+        self.best_survival = Duration::from_secs(bests.survival_secs);
+        self.high_score_file = Some(path);
+        self
+    }
+
+    /// Applies overrides from `SNAKE_*` environment variables.
+    ///
+    /// Precedence is defaults < profile < environment < CLI flags, so call
+    /// this after loading a profile and before applying command line
+    /// arguments. Invalid values are reported on stderr and ignored.
+    pub fn apply_env(&mut self) {
+        if let Some(width) = env_number("SNAKE_WIDTH") {
+            self.board_width = width;
+        }
+        if let Some(height) = env_number("SNAKE_HEIGHT") {
+            self.board_height = height;
+        }
+        if let Some(no_sound) = env_flag("SNAKE_NO_SOUND") {
+            self.enable_sound = !no_sound;
+        }
+        if let Some(no_color) = env_flag("SNAKE_NO_COLOR") {
+            self.enable_colors = !no_color;
+        }
+        if let Some(solid_walls) = env_flag("SNAKE_SOLID_WALLS") {
+            self.wall_wrapping = !solid_walls;
+        }
+        if let Some(theme) = env_theme("SNAKE_THEME") {
+            self.apply_theme(theme);
+        }
+    }
+
+    /// Loads the profile `name` from the default profile directory.
+    pub fn load_profile(name: &str) -> io::Result<Self> {
+        Self::load_profile_from(&default_profile_dir(), name)
+    }
+
+    /// Saves these settings as the profile `name` in the default profile
+    /// directory, returning the file written.
+    pub fn save_profile(&self, name: &str) -> io::Result<PathBuf> {
+        self.save_profile_to(&default_profile_dir(), name)
+    }
+
+    /// Loads the profile `name` from `dir`. A missing profile is reported
+    /// along with the names of the profiles that do exist.
+    pub fn load_profile_from(dir: &Path, name: &str) -> io::Result<Self> {
+        let contents = match fs::read_to_string(profile_path(dir, name)?) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                let available = list_profiles(dir);
+                let available = if available.is_empty() {
+                    String::from("none")
+                } else {
+                    available.join(", ")
+                };
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no profile named {:?} (available: {})", name, available),
+                ));
+            }
+            Err(err) => return Err(err),
+        };
+        serde_json::from_str(&contents).map_err(io::Error::other)
+    }
+
+    pub fn save_profile_to(&self, dir: &Path, name: &str) -> io::Result<PathBuf> {
+        let path = profile_path(dir, name)?;
+        fs::create_dir_all(dir)?;
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    /// Checks that the snake and food stand out from the background and from
+    /// each other. Always passes with colors disabled.
+    pub fn validate_colors(&self) -> Result<(), ConfigError> {
+        if !self.enable_colors {
+            return Ok(());
+        }
+
+        let pairs = [
+            (
+                "snake",
+                self.snake_color,
+                "background",
+                self.background_color,
+            ),
+            ("food", self.food_color, "background", self.background_color),
+            ("snake", self.snake_color, "food", self.food_color),
+        ];
+        for (first, color, second, other) in pairs {
+            if contrast_ratio(color, other).is_some_and(|ratio| ratio < MIN_CONTRAST_RATIO) {
+                return Err(ConfigError::LowContrast {
+                    first,
+                    second,
+                    color,
+                    other,
+                });
+            }
+        }
+        Ok(())
+    }
+
     pub fn update_high_score(&mut self, score: u32) {
         if score > self.high_score {
             self.high_score = score;
         }
     }
+
+    pub fn update_longest_snake(&mut self, length: usize) {
+        if length > self.longest_snake {
+            self.longest_snake = length;
+        }
+    }
+
+    pub fn update_best_survival(&mut self, survived: Duration) {
+        if survived > self.best_survival {
+            self.best_survival = survived;
+        }
+    }
+
+    /// Writes the personal bests for the current board size to the high
+    /// score file, keeping the records of other board sizes intact.
+    pub fn save_personal_bests(&self) -> io::Result<()> {
+        let Some(path) = &self.high_score_file else {
+            return Ok(());
+        };
+
+        let mut bests = load_personal_bests(path);
+        let current = PersonalBests {
+            score: self.high_score,
+            length: self.longest_snake,
+            survival_secs: self.best_survival.as_secs(),
+        };
+        let improved = bests
+            .entry((self.board_width, self.board_height))
+            .or_default()
+            .improve(&current);
+        if improved {
+            save_personal_bests(path, &bests)?;
+        }
+        Ok(())
+    }
+}
+
+/// WCAG contrast ratio between two colors, from 1.0 (identical luminance) to
+/// 21.0 (black on white). `None` if either is the terminal's default color,
+/// which can't be known.
+fn contrast_ratio(a: Color, b: Color) -> Option<f64> {
+    let (a, b) = (luminance(a)?, luminance(b)?);
+    Some((a.max(b) + 0.05) / (a.min(b) + 0.05))
+}
+
+/// Relative luminance of a color, using xterm's palette for named and
+/// indexed colors.
+fn luminance(color: Color) -> Option<f64> {
+    let (r, g, b) = approximate_rgb(color)?;
+    let channel = |value: u8| {
+        let value = f64::from(value) / 255.0;
+        if value <= 0.03928 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    Some(0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b))
+}
+
+fn approximate_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    const ANSI: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Indexed(index) => index,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+    };
+
+    Some(match index {
+        0..=15 => ANSI[usize::from(index)],
+        // 6x6x6 color cube
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+            (level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        // Grayscale ramp
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    })
+}
+
+/// Stores a `BorderType` by its name, since ratatui doesn't serialize it.
+mod border_type_name {
+    use ratatui::widgets::BorderType;
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(border: &BorderType, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(border)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BorderType, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Default directory for settings profiles: `snake_rs/profiles` in the
+/// platform's config directory, falling back to the current directory.
+pub fn default_profile_dir() -> PathBuf {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_default();
+    config_dir.join("snake_rs").join("profiles")
+}
+
+/// File for the profile `name` in `dir`. Names are limited to letters,
+/// digits, `_` and `-`, so a profile can't point outside `dir`.
+fn profile_path(dir: &Path, name: &str) -> io::Result<PathBuf> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "invalid profile name {:?} (use letters, digits, _ and -)",
+                name
+            ),
+        ));
+    }
+    Ok(dir.join(format!("{}.json", name)))
+}
+
+/// Names of the profiles saved in `dir`, sorted.
+fn list_profiles(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let is_json = path.extension().is_some_and(|ext| ext == "json");
+            is_json
+                .then(|| path.file_stem()?.to_str().map(String::from))
+                .flatten()
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn env_number(name: &str) -> Option<u16> {
+    let value = std::env::var(name).ok()?;
+    match value.trim().parse() {
+        Ok(number) if number > 0 => Some(number),
+        _ => {
+            eprintln!(
+                "warning: ignoring {}={:?}, expected a positive number",
+                name, value
+            );
+            None
+        }
+    }
+}
+
+fn env_flag(name: &str) -> Option<bool> {
+    let value = std::env::var(name).ok()?;
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" | "" => Some(false),
+        _ => {
+            eprintln!(
+                "warning: ignoring {}={:?}, expected true or false",
+                name, value
+            );
+            None
+        }
+    }
+}
+
+fn env_theme(name: &str) -> Option<Theme> {
+    let value = std::env::var(name).ok()?;
+    let theme = Theme::ALL
+        .into_iter()
+        .find(|theme| theme.name().eq_ignore_ascii_case(value.trim()));
+    if theme.is_none() {
+        let names: Vec<_> = Theme::ALL.iter().map(Theme::name).collect();
+        eprintln!(
+            "warning: ignoring {}={:?}, expected one of {}",
+            name,
+            value,
+            names.join(", ")
+        );
+    }
+    theme
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // Environment variables are process-wide, so tests touching them take turns
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Sets an environment variable for the lifetime of the guard.
+    struct EnvGuard {
+        name: &'static str,
+        previous: Option<String>,
+    }
+
+    impl EnvGuard {
+        fn set(name: &'static str, value: &str) -> Self {
+            let previous = std::env::var(name).ok();
+            std::env::set_var(name, value);
+            Self { name, previous }
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            match &self.previous {
+                Some(value) => std::env::set_var(self.name, value),
+                None => std::env::remove_var(self.name),
+            }
+        }
+    }
+
+    #[test]
+    fn test_thousands_score_format() {
+        let format = ScoreFormat::Thousands;
+        assert_eq!(format.format(0), "0");
+        assert_eq!(format.format(999), "999");
+        assert_eq!(format.format(1230), "1,230");
+        assert_eq!(format.format(100_000), "100,000");
+        assert_eq!(format.format(1_234_567), "1,234,567");
+        assert_eq!(format.format(u32::MAX), "4,294,967,295");
+    }
+
+    #[test]
+    fn test_zero_padded_score_format() {
+        let format = ScoreFormat::ZeroPadded;
+        assert_eq!(format.format(0), "000000");
+        assert_eq!(format.format(1230), "001230");
+        assert_eq!(format.format(999_999), "999999");
+        // Longer scores aren't cut off
+        assert_eq!(format.format(1_234_567), "1234567");
+        assert_eq!(ScoreFormat::Plain.format(1230), "1230");
+    }
+
+    #[test]
+    fn test_personal_bests_only_replaced_when_beaten() {
+        let mut config = GameConfig::default();
+
+        config.update_high_score(50);
+        config.update_high_score(30);
+        assert_eq!(config.high_score, 50);
+
+        config.update_longest_snake(12);
+        config.update_longest_snake(8);
+        assert_eq!(config.longest_snake, 12);
+
+        config.update_best_survival(Duration::from_secs(40));
+        config.update_best_survival(Duration::from_secs(25));
+        assert_eq!(config.best_survival, Duration::from_secs(40));
+
+        config.update_best_survival(Duration::from_secs(41));
+        assert_eq!(config.best_survival, Duration::from_secs(41));
+    }
+
+    #[test]
+    fn test_tick_rate_from_tick_ms_and_speed() {
+        assert_eq!(
+            GameConfig::default().tick_rate(),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            GameConfig::default().with_tick_ms(75).tick_rate(),
+            Duration::from_millis(75)
+        );
+        assert_eq!(
+            GameConfig::default().with_tick_ms(1).tick_rate(),
+            Duration::from_millis(MIN_TICK_MS)
+        );
+        assert_eq!(
+            GameConfig::default().with_speed(1).tick_rate(),
+            Duration::from_millis(230)
+        );
+        assert_eq!(
+            GameConfig::default().with_speed(MAX_SPEED).tick_rate(),
+            Duration::from_millis(50)
+        );
+    }
+
+    #[test]
+    fn test_validate_colors_flags_low_contrast() {
+        let invisible = GameConfig::default()
+            .with_snake_color(Color::Green)
+            .with_background_color(Color::Green);
+        assert!(matches!(
+            invisible.validate_colors(),
+            Err(ConfigError::LowContrast {
+                first: "snake",
+                second: "background",
+                ..
+            })
+        ));
+
+        let readable = GameConfig::default()
+            .with_snake_color(Color::LightGreen)
+            .with_food_color(Color::LightRed)
+            .with_background_color(Color::Black);
+        assert_eq!(readable.validate_colors(), Ok(()));
+
+        // Nothing to check when everything is drawn in the default colors
+        assert_eq!(invisible.with_colors(false).validate_colors(), Ok(()));
+    }
+
+    #[test]
+    fn test_apply_env_overrides_size_and_sound() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _width = EnvGuard::set("SNAKE_WIDTH", "25");
+        let _sound = EnvGuard::set("SNAKE_NO_SOUND", "1");
+
+        let mut config = GameConfig::new(30, 20);
+        config.apply_env();
+
+        assert_eq!(config.board_width, 25);
+        assert_eq!(config.board_height, 20);
+        assert!(!config.enable_sound);
+    }
+
+    #[test]
+    fn test_profile_round_trip() {
+        let dir = std::env::temp_dir().join(format!("snake_rs_profiles_{}", std::process::id()));
+        let config = GameConfig::new(25, 15)
+            .with_board_shape(BoardShape::Diamond)
+            .with_initial_direction(Direction::Left)
+            .with_snake_color(Color::Magenta)
+            .with_respawn_delay(3);
+
+        config.save_profile_to(&dir, "streaming").unwrap();
+        let loaded = GameConfig::load_profile_from(&dir, "streaming").unwrap();
+        let missing = GameConfig::load_profile_from(&dir, "hardcore").unwrap_err();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!((loaded.board_width, loaded.board_height), (25, 15));
+        assert_eq!(loaded.board_shape, BoardShape::Diamond);
+        assert_eq!(loaded.initial_direction, Direction::Left);
+        assert_eq!(loaded.snake_color, Color::Magenta);
+        assert_eq!(loaded.respawn_delay, 3);
+        assert_eq!(loaded.key_map, config.key_map);
+
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+        assert!(missing.to_string().contains("available: streaming"));
+    }
+
+    #[test]
+    fn test_profile_name_cannot_leave_profile_dir() {
+        let dir = std::env::temp_dir().join(format!("snake_rs_escape_{}", std::process::id()));
+        let config = GameConfig::default();
+
+        for name in ["../escaped", "nested/profile", "..", ""] {
+            let err = config.save_profile_to(&dir, name).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            let err = GameConfig::load_profile_from(&dir, name).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+        assert!(!dir.exists());
+        assert!(!dir.with_file_name("escaped.json").exists());
+    }
+
+    #[test]
+    fn test_apply_env_ignores_invalid_size() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _height = EnvGuard::set("SNAKE_HEIGHT", "tall");
+
+        let mut config = GameConfig::new(30, 20);
+        config.apply_env();
+
+        assert_eq!(config.board_height, 20);
+    }
+
+    #[test]
+    fn test_apply_env_sets_theme() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _theme = EnvGuard::set("SNAKE_THEME", "ocean");
+
+        let mut config = GameConfig::new(30, 20);
+        config.apply_env();
+        assert_eq!(config.snake_color, Color::Cyan);
+
+        let _theme = EnvGuard::set("SNAKE_THEME", "neon");
+        let mut config = GameConfig::new(30, 20);
+        config.apply_env();
+        assert_eq!(config.snake_color, GameConfig::new(30, 20).snake_color);
+    }
 }
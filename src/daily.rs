@@ -0,0 +1,79 @@
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// A calendar date in UTC, used to derive the daily challenge seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        Self { year, month, day }
+    }
+
+    pub fn today_utc() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        Self::from_days_since_epoch((seconds / SECONDS_PER_DAY) as i64)
+    }
+
+    /// Converts days since 1970-01-01 to a civil date (proleptic Gregorian).
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        // Howard Hinnant's civil_from_days algorithm
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        } as u32;
+        let year = (year_of_era + era * 400 + i64::from(month <= 2)) as i32;
+
+        Self { year, month, day }
+    }
+
+    /// The RNG seed for this date's challenge: the date read as `YYYYMMDD`.
+    pub fn seed(&self) -> u64 {
+        self.year as u64 * 10_000 + u64::from(self.month) * 100 + u64::from(self.day)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_maps_to_stable_seed() {
+        assert_eq!(Date::new(2025, 6, 1).seed(), 20250601);
+        assert_eq!(Date::new(2025, 6, 1).seed(), Date::new(2025, 6, 1).seed());
+        assert_ne!(Date::new(2025, 6, 1).seed(), Date::new(2025, 6, 2).seed());
+    }
+
+    #[test]
+    fn test_days_since_epoch_to_date() {
+        assert_eq!(Date::from_days_since_epoch(0), Date::new(1970, 1, 1));
+        assert_eq!(Date::from_days_since_epoch(20_240), Date::new(2025, 6, 1));
+        assert_eq!(Date::new(2024, 2, 29).to_string(), "2024-02-29");
+    }
+}
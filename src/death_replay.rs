@@ -0,0 +1,98 @@
+use std::collections::VecDeque;
+
+use crate::game::Position;
+
+/// Ticks kept for replaying the lead-up to a crash.
+pub const DEATH_REPLAY_TICKS: usize = 8;
+/// Ticks each replay frame stays on screen.
+pub const DEATH_REPLAY_SLOWDOWN: u32 = 3;
+
+/// Ring of the snake's most recent bodies, played back in slow motion after
+/// a crash. It only ever copies positions out of the game, so the replay
+/// can't change the score or the outcome.
+#[derive(Debug, Default)]
+pub struct DeathReplay {
+    history: VecDeque<VecDeque<Position>>,
+    frame: Option<usize>,
+    held: u32,
+}
+
+impl DeathReplay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remembers the body after a tick, forgetting the oldest one once the
+    /// ring is full.
+    pub fn record(&mut self, body: &VecDeque<Position>) {
+        if self.history.len() == DEATH_REPLAY_TICKS {
+            self.history.pop_front();
+        }
+        self.history.push_back(body.clone());
+    }
+
+    /// Starts playback from the oldest remembered tick.
+    pub fn start(&mut self) {
+        self.frame = (!self.history.is_empty()).then_some(0);
+        self.held = 0;
+    }
+
+    /// Advances playback by one app tick, finishing after the crash frame
+    /// has been held like the others.
+    pub fn tick(&mut self) {
+        let Some(frame) = self.frame else {
+            return;
+        };
+        self.held += 1;
+        if self.held < DEATH_REPLAY_SLOWDOWN {
+            return;
+        }
+        self.held = 0;
+        self.frame = (frame + 1 < self.history.len()).then_some(frame + 1);
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.frame.is_some()
+    }
+
+    /// Body to draw in place of the snake while the replay is playing.
+    pub fn current(&self) -> Option<&VecDeque<Position>> {
+        self.history.get(self.frame?)
+    }
+
+    /// Every remembered body, oldest first.
+    pub fn frames(&self) -> impl Iterator<Item = &VecDeque<Position>> {
+        self.history.iter()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_keeps_last_ticks_and_plays_them_slowly() {
+        let mut replay = DeathReplay::new();
+        for x in 0..10 {
+            replay.record(&VecDeque::from([Position::new(x, 0)]));
+        }
+        assert_eq!(replay.frames().count(), DEATH_REPLAY_TICKS);
+
+        replay.start();
+        let mut shown = Vec::new();
+        while let Some(body) = replay.current() {
+            shown.push(body[0].x);
+            replay.tick();
+        }
+
+        let expected: Vec<u16> = (2..10)
+            .flat_map(|x| std::iter::repeat_n(x, DEATH_REPLAY_SLOWDOWN as usize))
+            .collect();
+        assert_eq!(shown, expected);
+        assert!(!replay.is_playing());
+    }
+}
@@ -1,9 +1,31 @@
-use rand::{Rng, rngs::ThreadRng};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
-const INITIAL_SNAKE_LENGTH: usize = 4;
+use crate::config::GameConfig;
+use crate::power_up::PowerUpKind;
+use crate::scenario::{Scenario, ScenarioError};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Segments of a new snake, head included.
+const INITIAL_SNAKE_LENGTH: u16 = 5;
+const FOOD_POINTS: u32 = 10;
+const GOLDEN_FOOD_POINTS: u32 = 50;
+/// Smallest width or height a shrinking board closes in to.
+const MIN_SHRUNK_SIZE: u16 = 5;
+/// Default chance that a newly spawned food is golden.
+pub const GOLDEN_FOOD_CHANCE: f64 = 0.1;
+const BIG_FOOD_SIZE: u16 = 2;
+const BIG_FOOD_CHANCE: f64 = 0.2;
+const BIG_FOOD_MIN_BOARD: u16 = 10;
+const BIG_FOOD_SPAWN_ATTEMPTS: usize = 100;
+const POWER_UP_CHANCE: f64 = 0.1;
+const POWER_UP_SPAWN_ATTEMPTS: usize = 100;
+/// Segments of the snake that respawns after a lost life, at least one.
+const RESPAWN_LENGTH: u16 = INITIAL_SNAKE_LENGTH / 2;
+/// Points lost each time the snake is teleported instead of dying.
+const RESPAWN_PENALTY: u32 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Position {
     pub x: u16,
     pub y: u16,
@@ -13,9 +35,36 @@ impl Position {
     pub fn new(x: u16, y: u16) -> Self {
         Self { x, y }
     }
+
+    /// The neighboring cell in `direction`, or `None` past the top or left
+    /// edge where coordinates would go negative.
+    pub fn step(&self, direction: Direction) -> Option<Position> {
+        let (dx, dy) = direction.delta();
+        Some(Position::new(
+            self.x.checked_add_signed(dx as i16)?,
+            self.y.checked_add_signed(dy as i16)?,
+        ))
+    }
+
+    /// The neighboring cell in `direction` on a `board_width` x
+    /// `board_height` board whose edges all wrap.
+    pub fn wrapping_step(
+        &self,
+        direction: Direction,
+        board_width: u16,
+        board_height: u16,
+    ) -> Position {
+        wrapped_position(*self, direction, board_width, board_height, WrapEdges::ALL)
+            .expect("every edge wraps")
+    }
+
+    /// Cells between the two positions moving only along rows and columns.
+    pub fn manhattan_distance(&self, other: Position) -> u32 {
+        u32::from(self.x.abs_diff(other.x)) + u32::from(self.y.abs_diff(other.y))
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Direction {
     Up,
     Down,
@@ -32,13 +81,161 @@ impl Direction {
             Direction::Right => Direction::Left,
         }
     }
+
+    /// Column and row change of one step in this direction.
+    pub fn delta(&self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Kind of the regular food on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FoodKind {
+    #[default]
+    Normal,
+    /// Rarer food worth more points.
+    Golden,
+}
+
+impl FoodKind {
+    pub fn points(&self) -> u32 {
+        match self {
+            FoodKind::Normal => FOOD_POINTS,
+            FoodKind::Golden => GOLDEN_FOOD_POINTS,
+        }
+    }
+}
+
+/// Which board edges wrap to the opposite side while wrapping is on. Moving
+/// off any other edge is a crash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WrapEdges {
+    pub top: bool,
+    pub bottom: bool,
+    pub left: bool,
+    pub right: bool,
+}
+
+impl Default for WrapEdges {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl WrapEdges {
+    pub const ALL: Self = Self {
+        top: true,
+        bottom: true,
+        left: true,
+        right: true,
+    };
+
+    pub const NONE: Self = Self {
+        top: false,
+        bottom: false,
+        left: false,
+        right: false,
+    };
+
+    /// Whether the edge a snake heading in `direction` runs into wraps.
+    pub fn wraps(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::Up => self.top,
+            Direction::Down => self.bottom,
+            Direction::Left => self.left,
+            Direction::Right => self.right,
+        }
+    }
+}
+
+/// Where a head at `head` ends up after one step in `direction` on a
+/// `board_width` x `board_height` board. Crossing an edge in `wrap` lands on
+/// the opposite side; crossing any other edge gives `None`, for the caller
+/// to treat as a wall. Each axis is handled on its own, so a step across a
+/// corner wraps both ways.
+pub fn wrapped_position(
+    head: Position,
+    direction: Direction,
+    board_width: u16,
+    board_height: u16,
+    wrap: WrapEdges,
+) -> Option<Position> {
+    let (dx, dy) = direction.delta();
+    Some(Position::new(
+        wrap_axis(head.x, dx, board_width, wrap.left, wrap.right)?,
+        wrap_axis(head.y, dy, board_height, wrap.top, wrap.bottom)?,
+    ))
+}
+
+/// Steps `value` by `delta` along an axis of `size` cells, or `None` if
+/// that leaves through an edge that doesn't wrap.
+fn wrap_axis(value: u16, delta: i32, size: u16, wrap_low: bool, wrap_high: bool) -> Option<u16> {
+    let next = i32::from(value) + delta;
+    let size = i32::from(size);
+    let next = if next < 0 {
+        wrap_low.then_some(size - 1)?
+    } else if next >= size {
+        wrap_high.then_some(0)?
+    } else {
+        next
+    };
+    Some(next as u16)
+}
+
+/// Outline of the playfield. Cells outside the shape inscribed in the board
+/// act as walls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BoardShape {
+    #[default]
+    Rectangle,
+    Circle,
+    Diamond,
+}
+
+impl BoardShape {
+    /// Whether `pos` lies inside this shape on a `width` x `height` board.
+    pub fn contains(&self, pos: Position, width: u16, height: u16) -> bool {
+        if pos.x >= width || pos.y >= height {
+            return false;
+        }
+
+        // Distances from the board center, normalized so the shape's edge is 1.0
+        let dx = (f64::from(pos.x) - f64::from(width - 1) / 2.0).abs() / (f64::from(width) / 2.0);
+        let dy = (f64::from(pos.y) - f64::from(height - 1) / 2.0).abs() / (f64::from(height) / 2.0);
+
+        match self {
+            BoardShape::Rectangle => true,
+            BoardShape::Circle => dx * dx + dy * dy <= 1.0,
+            BoardShape::Diamond => dx + dy <= 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameState {
     Playing,
     Paused,
     GameOver,
+    Won,
+}
+
+/// Copy of what's on the board at one moment, detached from the live game so
+/// it can be sent to another thread or exported as JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub board_width: u16,
+    pub board_height: u16,
+    /// Snake segments, head first.
+    pub snake: Vec<Position>,
+    pub direction: Direction,
+    pub food: Option<Position>,
+    pub score: u32,
+    pub state: GameState,
 }
 
 pub struct Snake {
@@ -56,6 +253,19 @@ impl Snake {
         }
     }
 
+    /// Snake laid out along `body`, head first, without moving it there.
+    ///
+    /// # Panics
+    ///
+    /// If `body` is empty.
+    pub fn with_body(body: Vec<Position>, direction: Direction) -> Self {
+        assert!(!body.is_empty(), "a snake needs at least a head");
+        Self {
+            body: body.into(),
+            direction,
+        }
+    }
+
     pub fn head(&self) -> Position {
         *self.body.front().unwrap()
     }
@@ -76,54 +286,33 @@ impl Snake {
         }
     }
 
-    pub fn move_forward(&mut self) -> Position {
-        let head = self.head();
-        let new_head = match self.direction {
-            Direction::Up => Position::new(head.x, head.y.saturating_sub(1)),
-            Direction::Down => Position::new(head.x, head.y + 1),
-            Direction::Left => Position::new(head.x.saturating_sub(1), head.y),
-            Direction::Right => Position::new(head.x + 1, head.y),
-        };
-
+    /// Moves the head to `new_head` and drops the tail, returning the
+    /// dropped tail so the caller can put it back if the snake ate.
+    pub fn advance(&mut self, new_head: Position) -> Position {
         self.body.push_front(new_head);
-        self.body.pop_back().unwrap()
+        self.body.pop_back().expect("a snake always has a head")
     }
 
-    pub fn move_forward_with_wrapping(&mut self, board_width: u16, board_height: u16) -> Position {
-        let head = self.head();
-        let new_head = match self.direction {
-            Direction::Up => {
-                if head.y == 0 {
-                    Position::new(head.x, board_height - 1) // Wrap to bottom
-                } else {
-                    Position::new(head.x, head.y - 1)
-                }
-            }
-            Direction::Down => {
-                if head.y >= board_height - 1 {
-                    Position::new(head.x, 0) // Wrap to top
-                } else {
-                    Position::new(head.x, head.y + 1)
-                }
-            }
-            Direction::Left => {
-                if head.x == 0 {
-                    Position::new(board_width - 1, head.y) // Wrap to right
-                } else {
-                    Position::new(head.x - 1, head.y)
-                }
-            }
-            Direction::Right => {
-                if head.x >= board_width - 1 {
-                    Position::new(0, head.y) // Wrap to left
-                } else {
-                    Position::new(head.x + 1, head.y)
-                }
-            }
-        };
+    /// Lengthens the snake by `n` segments stacked on its tail. They unfold
+    /// one per move as the rest of the body moves on.
+    pub fn grow_by(&mut self, n: usize) {
+        let tail = *self.body.back().expect("a snake always has a head");
+        self.body.extend(std::iter::repeat_n(tail, n));
+    }
 
-        self.body.push_front(new_head);
-        self.body.pop_back().unwrap()
+    /// Moves one cell in the current direction and returns the dropped
+    /// tail. Returns `None` without moving if the head would leave the top
+    /// or left edge.
+    pub fn move_forward(&mut self) -> Option<Position> {
+        let new_head = self.head().step(self.direction)?;
+        Some(self.advance(new_head))
+    }
+
+    pub fn move_forward_with_wrapping(&mut self, board_width: u16, board_height: u16) -> Position {
+        let new_head = self
+            .head()
+            .wrapping_step(self.direction, board_width, board_height);
+        self.advance(new_head)
     }
 
     pub fn grow(&mut self, old_tail: Position) {
@@ -131,8 +320,22 @@ impl Snake {
     }
 
     pub fn check_self_collision(&self) -> bool {
+        self.collides_with_body(false)
+    }
+
+    /// Whether the head shares a cell with the rest of the body. With
+    /// `neck_grace` the segment that was right behind the head before the
+    /// last move doesn't count, so a move that doubles back onto it isn't a
+    /// collision. Since the move already happened, that segment now sits at
+    /// index 2: index 1 is where the head just came from.
+    pub fn collides_with_body(&self, neck_grace: bool) -> bool {
         let head = self.head();
-        self.body.iter().skip(1).any(|&pos| pos == head)
+        let neck = if neck_grace { Some(2) } else { None };
+        self.body
+            .iter()
+            .enumerate()
+            .skip(1)
+            .any(|(index, &pos)| pos == head && Some(index) != neck)
     }
 
     pub fn len(&self) -> usize {
@@ -147,49 +350,319 @@ impl Snake {
 
 pub struct Game {
     snake: Snake,
-    food: Position,
+    /// Direction of the snake's most recent move. Turns are checked against
+    /// this rather than the pending direction, so several quick turns within
+    /// one tick can't add up to a reversal.
+    committed_direction: Direction,
+    initial_direction: Direction,
+    food: Option<Position>,
+    food_kind: FoodKind,
+    golden_food_chance: f64,
+    food_avoids_next_head: bool,
+    big_food: Option<(Position, u16)>,
+    power_up: Option<(Position, PowerUpKind)>,
+    last_eaten: Option<Position>,
+    last_wrap: Option<Direction>,
+    wrap_count: u32,
     score: u32,
+    food_eaten: u32,
     state: GameState,
     board_width: u16,
     board_height: u16,
     wall_wrapping: bool,
-    rng: ThreadRng,
+    wrap_after_score: Option<u32>,
+    wrap_edges: WrapEdges,
+    wall_grace_ticks: u32,
+    wait_for_input: bool,
+    /// Length the snake stops growing at, if any.
+    max_length: Option<usize>,
+    /// Cells food never spawns on. Solid ones are also fatal to run into.
+    obstacles: Vec<Position>,
+    solid_obstacles: bool,
+    /// Cells that are always fatal to run into, unlike soft obstacles.
+    spikes: Vec<Position>,
+    /// Position every game starts from instead of the usual snake.
+    scenario: Option<Scenario>,
+    /// The snake holds still until the first direction is set.
+    awaiting_input: bool,
+    /// Ticks left in which walls wrap instead of killing.
+    grace_ticks_left: u32,
+    peaceful: bool,
+    self_collision: bool,
+    neck_grace: bool,
+    board_shape: BoardShape,
+    hunger_rate: Option<u32>,
+    keep_score_on_restart: bool,
+    ticks_since_food: u32,
+    /// Meals within this many ticks of the last one count as a combo. 0
+    /// disables combos.
+    combo_window: u32,
+    combo_multiplier: u32,
+    /// Combo meals in a row so far.
+    combo: u32,
+    respawn_delay: u32,
+    respawn_on_death: bool,
+    /// Ticks left until the next food appears after eating one.
+    respawn_countdown: u32,
+    max_lives: u32,
+    lives: u32,
+    shrink_interval: u32,
+    /// Rings of cells around the edge that have closed in so far.
+    shrink_inset: u16,
+    ticks_until_shrink: u32,
+    seed: Option<u64>,
+    rng: StdRng,
 }
 
 impl Game {
     pub fn new(board_width: u16, board_height: u16) -> Self {
-        let start_pos = Position::new(board_width / 2, board_height / 3);
-        let mut game = Self {
-            snake: Snake::new(start_pos),
-            food: Position::new(0, 0),
-            score: 0,
-            state: GameState::Playing,
+        let mut game = Self::with_rng(
             board_width,
             board_height,
-            wall_wrapping: false, // Default to false for backward compatibility
-            rng: rand::rng(),
+            None,
+            StdRng::from_rng(&mut rand::rng()),
+        );
+        game.spawn_food();
+        game
+    }
+
+    /// Creates a game whose food placement is fully determined by `seed`.
+    /// Restarting a seeded game replays the same food sequence.
+    pub fn with_seed(board_width: u16, board_height: u16, seed: u64) -> Self {
+        let mut game = Self::with_rng(
+            board_width,
+            board_height,
+            Some(seed),
+            StdRng::seed_from_u64(seed),
+        );
+        game.spawn_food();
+        game
+    }
+
+    /// Creates a game with every gameplay setting taken from `config`.
+    pub fn from_config(config: &GameConfig) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
         };
-        game.grow_to_initial_length(INITIAL_SNAKE_LENGTH);
+        let mut game = Self::with_rng(config.board_width, config.board_height, config.seed, rng);
+        game.set_wall_wrapping(config.wall_wrapping);
+        game.set_wrap_after_score(config.wrap_after_score);
+        game.set_wrap_edges(config.wrap_edges);
+        game.set_wall_grace_ticks(config.wall_grace_ticks);
+        game.set_wait_for_input(config.wait_for_input);
+        game.set_max_length(config.max_length);
+        game.set_obstacles(config.obstacles.clone());
+        game.set_solid_obstacles(config.solid_obstacles);
+        game.set_spikes(config.spikes.clone());
+        game.set_peaceful(config.peaceful);
+        game.set_self_collision(config.self_collision);
+        game.set_neck_grace(config.neck_grace);
+        game.board_shape = config.board_shape;
+        game.set_hunger(config.hunger_rate);
+        game.set_combo(config.combo_window, config.combo_multiplier);
+        game.set_golden_food_chance(config.golden_food_chance);
+        game.set_food_avoids_next_head(config.food_avoids_next_head);
+        game.set_keep_score_on_restart(config.keep_score_on_restart);
+        game.set_respawn_delay(config.respawn_delay);
+        game.set_respawn_on_death(config.respawn_on_death);
+        game.set_shrink_interval(config.shrink_interval);
+        game.set_lives(config.lives);
+        game.initial_direction = config.initial_direction;
+        game.snake = initial_snake(
+            config.board_width,
+            config.board_height,
+            config.board_height / 3,
+            config.initial_direction,
+            INITIAL_SNAKE_LENGTH,
+        );
+        game.committed_direction = config.initial_direction;
+        if config.start_paused {
+            game.state = GameState::Paused;
+        }
+        // Only place the first food once its shape and kind settings apply
         game.spawn_food();
+        if let Some(scenario) = &config.scenario {
+            game.apply_scenario(scenario);
+            game.scenario = Some(scenario.clone());
+        }
         game
     }
 
+    /// Game set up exactly as `scenario` describes, with default settings.
+    /// Restarting returns to the scenario.
+    pub fn from_scenario(scenario: &Scenario) -> Result<Self, ScenarioError> {
+        scenario.validate()?;
+        let config = GameConfig::new(scenario.board_width, scenario.board_height)
+            .with_scenario(scenario.clone());
+        Ok(Self::from_config(&config))
+    }
+
+    /// Places the snake, food and score from `scenario`.
+    fn apply_scenario(&mut self, scenario: &Scenario) {
+        self.snake = Snake::with_body(scenario.body.clone(), scenario.direction);
+        self.committed_direction = scenario.direction;
+        self.score = scenario.score;
+        self.big_food = None;
+        self.power_up = None;
+        if let Some(food) = scenario.food {
+            self.food = Some(food);
+            self.food_kind = FoodKind::Normal;
+        } else if self
+            .food
+            .is_some_and(|food| self.snake.body().contains(&food))
+        {
+            self.spawn_food();
+        }
+    }
+
+    /// Creates a game without any food placed yet.
+    fn with_rng(board_width: u16, board_height: u16, seed: Option<u64>, rng: StdRng) -> Self {
+        Self {
+            snake: initial_snake(
+                board_width,
+                board_height,
+                board_height / 3,
+                Direction::Right,
+                INITIAL_SNAKE_LENGTH,
+            ),
+            committed_direction: Direction::Right,
+            initial_direction: Direction::Right,
+            food: None,
+            food_kind: FoodKind::Normal,
+            golden_food_chance: GOLDEN_FOOD_CHANCE,
+            food_avoids_next_head: false,
+            big_food: None,
+            power_up: None,
+            last_eaten: None,
+            last_wrap: None,
+            wrap_count: 0,
+            score: 0,
+            food_eaten: 0,
+            state: GameState::Playing,
+            board_width,
+            board_height,
+            wall_wrapping: false, // Default to false for backward compatibility
+            wrap_after_score: None,
+            wrap_edges: WrapEdges::ALL,
+            wall_grace_ticks: 0,
+            wait_for_input: false,
+            max_length: None,
+            obstacles: Vec::new(),
+            solid_obstacles: true,
+            spikes: Vec::new(),
+            scenario: None,
+            awaiting_input: false,
+            grace_ticks_left: 0,
+            peaceful: false,
+            self_collision: true,
+            neck_grace: false,
+            board_shape: BoardShape::Rectangle,
+            hunger_rate: None,
+            keep_score_on_restart: false,
+            ticks_since_food: 0,
+            combo_window: 0,
+            combo_multiplier: 1,
+            combo: 0,
+            respawn_delay: 0,
+            respawn_on_death: false,
+            respawn_countdown: 0,
+            max_lives: 1,
+            lives: 1,
+            shrink_interval: 0,
+            shrink_inset: 0,
+            ticks_until_shrink: 0,
+            seed,
+            rng,
+        }
+    }
+
     pub fn snake(&self) -> &Snake {
         &self.snake
     }
 
-    pub fn food(&self) -> Position {
+    /// The current food, or `None` while waiting for it to respawn.
+    pub fn food(&self) -> Option<Position> {
         self.food
     }
 
+    pub fn food_kind(&self) -> FoodKind {
+        self.food_kind
+    }
+
+    /// Chance from 0.0 to 1.0 that newly spawned food is golden. Takes
+    /// effect from the next food.
+    pub fn set_golden_food_chance(&mut self, chance: f64) {
+        self.golden_food_chance = chance.clamp(0.0, 1.0);
+    }
+
+    /// Keeps new food out of the cell the head moves into next tick, where
+    /// it would be eaten before it's ever seen. Food still goes there if
+    /// it's the only free cell.
+    pub fn set_food_avoids_next_head(&mut self, enable: bool) {
+        self.food_avoids_next_head = enable;
+    }
+
+    /// Top-left corner and side length of the big food, if one is on the board.
+    pub fn big_food(&self) -> Option<(Position, u16)> {
+        self.big_food
+    }
+
+    pub fn power_up(&self) -> Option<(Position, PowerUpKind)> {
+        self.power_up
+    }
+
+    /// Places a power-up at `pos`, replacing any existing one.
+    pub fn place_power_up(&mut self, pos: Position, kind: PowerUpKind) {
+        self.power_up = Some((pos, kind));
+    }
+
+    /// Cell where food was eaten on the most recent tick, if any.
+    pub fn last_eaten(&self) -> Option<Position> {
+        self.last_eaten
+    }
+
+    /// Direction the snake was heading if it wrapped around an edge on the
+    /// most recent tick.
+    pub fn last_wrap(&self) -> Option<Direction> {
+        self.last_wrap
+    }
+
+    /// Times the snake has wrapped around an edge this game.
+    pub fn wrap_count(&self) -> u32 {
+        self.wrap_count
+    }
+
     pub fn score(&self) -> u32 {
         self.score
     }
 
+    /// Pieces of food eaten this game, counting big food as one.
+    pub fn food_eaten(&self) -> u32 {
+        self.food_eaten
+    }
+
     pub fn state(&self) -> GameState {
         self.state
     }
 
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn board_width(&self) -> u16 {
+        self.board_width
+    }
+
+    pub fn board_height(&self) -> u16 {
+        self.board_height
+    }
+
+    /// Board size as `(width, height)` in cells.
+    pub fn dimensions(&self) -> (u16, u16) {
+        (self.board_width, self.board_height)
+    }
+
     pub fn set_wall_wrapping(&mut self, enabled: bool) {
         self.wall_wrapping = enabled;
     }
@@ -199,152 +672,2197 @@ impl Game {
         self.wall_wrapping
     }
 
-    pub fn set_direction(&mut self, direction: Direction) {
-        if self.state == GameState::Playing {
-            self.snake.set_direction(direction);
-        }
+    /// Limits wrapping to some edges of the board; the others are walls
+    /// even while wrapping is on.
+    pub fn set_wrap_edges(&mut self, edges: WrapEdges) {
+        self.wrap_edges = edges;
     }
 
-    pub fn toggle_pause(&mut self) {
-        match self.state {
-            GameState::Playing => self.state = GameState::Paused,
-            GameState::Paused => self.state = GameState::Playing,
-            GameState::GameOver => {}
-        }
+    pub fn wrap_edges(&self) -> WrapEdges {
+        self.wrap_edges
+    }
+
+    /// Whether a snake heading in `direction` would wrap at the edge rather
+    /// than crash into it.
+    pub fn wraps_towards(&self, direction: Direction) -> bool {
+        // Peaceful mode can't die, so every edge wraps there
+        let wraps = self.is_wrapping() && (self.peaceful || self.wrap_edges.wraps(direction));
+        let in_grace = self.grace_ticks_left > 0
+            && self.board_shape == BoardShape::Rectangle
+            && self.shrink_inset == 0;
+        wraps || in_grace
+    }
+
+    /// Holds the snake still at the start of every game until the first
+    /// `set_direction`. Pausing doesn't bring the wait back.
+    pub fn set_wait_for_input(&mut self, enable: bool) {
+        self.wait_for_input = enable;
+        self.awaiting_input = enable;
+    }
+
+    /// Stops the snake growing once it's `max_length` long; food still
+    /// scores. `None` lets it grow without limit.
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.max_length = max_length;
     }
-    fn grow_to_initial_length(&mut self, length: usize) {
-        for _ in 0..length {
-            let tail = self.snake.move_forward();
-            self.snake.grow(tail);
+
+    /// Places obstacles on the board. Food is moved off them if needed.
+    pub fn set_obstacles(&mut self, obstacles: Vec<Position>) {
+        self.obstacles = obstacles;
+        if self.food.is_some_and(|food| self.obstacles.contains(&food)) {
+            self.spawn_food();
         }
     }
 
-    pub fn reset(&mut self) {
-        let start_pos = Position::new(self.board_width / 2, self.board_height / 2);
-        self.snake = Snake::new(start_pos);
-        self.grow_to_initial_length(INITIAL_SNAKE_LENGTH);
-        self.score = 0;
-        self.state = GameState::Playing;
-        // Note: wall_wrapping setting is preserved during reset
-        self.spawn_food();
+    pub fn obstacles(&self) -> &[Position] {
+        &self.obstacles
     }
 
-    pub fn update(&mut self) -> GameEvent {
-        if self.state != GameState::Playing {
-            return GameEvent::None;
+    /// Solid obstacles (the default) end the game like a wall, even while
+    /// the edges wrap. Soft ones let the snake pass and only keep food off.
+    pub fn set_solid_obstacles(&mut self, solid: bool) {
+        self.solid_obstacles = solid;
+    }
+
+    pub fn solid_obstacles(&self) -> bool {
+        self.solid_obstacles
+    }
+
+    /// Places spikes: deadly cells, even while the edges wrap. Food still
+    /// spawns right next to them, just never on them.
+    pub fn set_spikes(&mut self, spikes: Vec<Position>) {
+        self.spikes = spikes;
+        if self.food.is_some_and(|food| self.spikes.contains(&food)) {
+            self.spawn_food();
         }
+    }
 
-        let old_tail = if self.wall_wrapping {
-            self.snake
-                .move_forward_with_wrapping(self.board_width, self.board_height)
-        } else {
-            self.snake.move_forward()
+    pub fn spikes(&self) -> &[Position] {
+        &self.spikes
+    }
+
+    /// Whether `pos` is an obstacle the snake dies on.
+    fn is_solid_obstacle(&self, pos: Position) -> bool {
+        self.solid_obstacles && !self.peaceful && self.obstacles.contains(&pos)
+    }
+
+    /// Whether the snake is still waiting for the first direction.
+    pub fn is_awaiting_input(&self) -> bool {
+        self.awaiting_input
+    }
+
+    /// Makes every wall wrap for the first `ticks` ticks of each snake, so
+    /// a start next to a wall isn't an instant death. 0 disables the grace.
+    pub fn set_wall_grace_ticks(&mut self, ticks: u32) {
+        self.wall_grace_ticks = ticks;
+        self.grace_ticks_left = ticks;
+    }
+
+    /// Walls start solid and wrap once the score reaches `threshold`,
+    /// overriding the wall wrapping setting. `None` keeps the static setting.
+    pub fn set_wrap_after_score(&mut self, threshold: Option<u32>) {
+        self.wrap_after_score = threshold;
+    }
+
+    /// Whether moving off an edge currently wraps to the opposite side.
+    pub fn is_wrapping(&self) -> bool {
+        // Peaceful mode always wraps regardless of the wall setting, but only
+        // a full-size rectangular board can wrap at all
+        let walls_wrap = match self.wrap_after_score {
+            Some(threshold) => self.score >= threshold,
+            None => self.wall_wrapping,
         };
+        self.board_shape == BoardShape::Rectangle
+            && self.shrink_inset == 0
+            && (walls_wrap || self.peaceful)
+    }
 
-        let head = self.snake.head();
+    /// In peaceful mode collisions never end the game: walls always wrap
+    /// and running into yourself is ignored.
+    pub fn set_peaceful(&mut self, enabled: bool) {
+        self.peaceful = enabled;
+    }
 
-        // Check wall collision only if wrapping is disabled
-        if !self.wall_wrapping && self.is_out_of_bounds(head) {
-            self.state = GameState::GameOver;
-            return GameEvent::GameOver;
-        }
+    pub fn peaceful(&self) -> bool {
+        self.peaceful
+    }
 
-        // Check self collision
-        if self.snake.check_self_collision() {
-            self.state = GameState::GameOver;
-            return GameEvent::GameOver;
-        }
+    /// Whether running into your own body ends the game. Turning it off is
+    /// meant for practice; walls still count.
+    pub fn set_self_collision(&mut self, enabled: bool) {
+        self.self_collision = enabled;
+    }
 
-        // Check food collision
-        if head == self.food {
-            self.snake.grow(old_tail);
-            self.score += 10;
+    pub fn self_collision(&self) -> bool {
+        self.self_collision
+    }
+
+    /// Forgives a move that lands on the neck, the segment right behind the
+    /// head. Turning straight back is already blocked by `set_direction`,
+    /// but a buffered turn can still aim the head there, and classic rules
+    /// count that as a crash. Other body segments still count, and so does
+    /// the neck once the snake has moved on from it. Off by default.
+    pub fn set_neck_grace(&mut self, enabled: bool) {
+        self.neck_grace = enabled;
+    }
+
+    pub fn neck_grace(&self) -> bool {
+        self.neck_grace
+    }
+
+    /// Non-rectangular shapes always have solid walls, even with wall
+    /// wrapping or peaceful mode enabled, since wrapping around the board
+    /// edge would land outside the shape.
+    pub fn set_board_shape(&mut self, shape: BoardShape) {
+        self.board_shape = shape;
+        if self.food.is_some_and(|food| self.is_out_of_bounds(food)) {
             self.spawn_food();
-            return GameEvent::FoodEaten;
         }
+    }
 
-        GameEvent::Moved
+    pub fn board_shape(&self) -> BoardShape {
+        self.board_shape
     }
 
-    fn is_out_of_bounds(&self, pos: Position) -> bool {
-        pos.x >= self.board_width || pos.y >= self.board_height
+    /// With hunger the score drops by one point every `rate` ticks without
+    /// eating, down to zero. `None` disables it.
+    pub fn set_hunger(&mut self, rate: Option<u32>) {
+        self.hunger_rate = rate;
     }
 
-    fn spawn_food(&mut self) {
-        loop {
-            let x = self.rng.random_range(0..self.board_width);
-            let y = self.rng.random_range(0..self.board_height);
-            let food_pos = Position::new(x, y);
+    /// Multiplies the points of food eaten within `window` ticks of the
+    /// previous meal by `multiplier`. A `window` of 0 disables combos.
+    pub fn set_combo(&mut self, window: u32, multiplier: u32) {
+        self.combo_window = window;
+        self.combo_multiplier = multiplier.max(1);
+    }
 
-            // Make sure food doesn't spawn on snake
-            if !self.snake.body().contains(&food_pos) {
-                self.food = food_pos;
-                break;
-            }
+    /// Combo meals in a row, or 0 once the window for the next one has
+    /// passed.
+    pub fn combo(&self) -> u32 {
+        if self.ticks_since_food < self.combo_window {
+            self.combo
+        } else {
+            0
         }
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum GameEvent {
-    None,
-    Moved,
-    FoodEaten,
-    GameOver,
-}
+    /// Ticks left to eat again and extend the combo, counting down from the
+    /// combo window after every meal. 0 with combos disabled or before the
+    /// first meal.
+    pub fn combo_ticks_left(&self) -> u32 {
+        if self.food_eaten == 0 {
+            return 0;
+        }
+        self.combo_window.saturating_sub(self.ticks_since_food)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn combo_window(&self) -> u32 {
+        self.combo_window
+    }
 
-    #[test]
-    fn test_wall_wrapping_horizontal() {
-        let mut game = Game::new(5, 5);
-        game.set_wall_wrapping(true);
+    /// Carries the score over into the next game on `reset`, for totalling
+    /// up several practice runs.
+    pub fn set_keep_score_on_restart(&mut self, enabled: bool) {
+        self.keep_score_on_restart = enabled;
+    }
 
-        // Position snake at the right edge
-        game.snake.body.clear();
-        game.snake.body.push_back(Position::new(4, 2));
-        game.snake.set_direction(Direction::Right);
+    /// Leaves the board without food for `ticks` ticks after each meal.
+    pub fn set_respawn_delay(&mut self, ticks: u32) {
+        self.respawn_delay = ticks;
+    }
 
-        // Move forward - should wrap to left side
-        game.update();
+    /// Instead of ending the game or costing a life, a crash teleports the
+    /// snake to a random free cell as a single segment and costs a few
+    /// points. The game only ends if there's no free cell left.
+    pub fn set_respawn_on_death(&mut self, enabled: bool) {
+        self.respawn_on_death = enabled;
+    }
 
-        assert_eq!(game.snake.head().x, 0);
-        assert_eq!(game.snake.head().y, 2);
-        assert_eq!(game.state, GameState::Playing);
+    /// Closes in the outermost open ring of cells every `ticks` ticks, down
+    /// to a small core. 0 keeps the board at full size.
+    pub fn set_shrink_interval(&mut self, ticks: u32) {
+        self.shrink_interval = ticks;
+        self.ticks_until_shrink = ticks;
     }
 
-    #[test]
-    fn test_wall_wrapping_vertical() {
-        let mut game = Game::new(5, 5);
-        game.set_wall_wrapping(true);
+    /// Number of crashes the player can survive plus one, so 1 means the
+    /// first crash ends the game.
+    pub fn set_lives(&mut self, lives: u32) {
+        self.max_lives = lives.max(1);
+        self.lives = self.max_lives;
+    }
 
-        // Position snake at the top edge
-        game.snake.body.clear();
-        game.snake.body.push_back(Position::new(2, 0));
-        game.snake.set_direction(Direction::Up);
+    /// Lives left, counting the current one.
+    pub fn lives(&self) -> u32 {
+        self.lives
+    }
 
-        // Move forward - should wrap to bottom side
-        game.update();
+    /// Rings of cells around the edge that have been walled off.
+    pub fn shrink_inset(&self) -> u16 {
+        self.shrink_inset
+    }
 
-        assert_eq!(game.snake.head().x, 2);
-        assert_eq!(game.snake.head().y, 4);
-        assert_eq!(game.state, GameState::Playing);
+    /// Whether `pos` is inside the current playing area, accounting for the
+    /// board shape and any shrinking.
+    pub fn is_playable(&self, pos: Position) -> bool {
+        !self.is_out_of_bounds(pos)
     }
 
-    #[test]
-    fn test_no_wall_wrapping() {
-        let mut game = Game::new(5, 5);
+    pub fn ticks_since_food(&self) -> u32 {
+        self.ticks_since_food
+    }
 
-        // Position snake at the right edge
-        game.snake.body.clear();
-        game.snake.body.push_back(Position::new(4, 2));
-        game.snake.set_direction(Direction::Right);
+    pub fn set_direction(&mut self, direction: Direction) {
+        if self.state != GameState::Playing {
+            return;
+        }
+        // Any movement key starts the game, even one that can't turn
+        self.awaiting_input = false;
+        if direction != self.committed_direction.opposite() {
+            self.snake.direction = direction;
+        }
+    }
+
+    pub fn toggle_pause(&mut self) {
+        match self.state {
+            GameState::Playing => self.state = GameState::Paused,
+            GameState::Paused => self.state = GameState::Playing,
+            GameState::GameOver | GameState::Won => {}
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.snake = initial_snake(
+            self.board_width,
+            self.board_height,
+            self.board_height / 2,
+            self.initial_direction,
+            INITIAL_SNAKE_LENGTH,
+        );
+        self.committed_direction = self.snake.direction;
+        if !self.keep_score_on_restart {
+            self.score = 0;
+        }
+        self.food_eaten = 0;
+        self.state = GameState::Playing;
+        // Note: wall_wrapping setting is preserved during reset, and so is the
+        // score if it's kept on restart
+        self.big_food = None;
+        self.power_up = None;
+        self.last_eaten = None;
+        self.last_wrap = None;
+        self.wrap_count = 0;
+        self.ticks_since_food = 0;
+        self.combo = 0;
+        self.food = None;
+        self.respawn_countdown = 0;
+        self.shrink_inset = 0;
+        self.ticks_until_shrink = self.shrink_interval;
+        self.lives = self.max_lives;
+        self.grace_ticks_left = self.wall_grace_ticks;
+        self.awaiting_input = self.wait_for_input;
+        if let Some(seed) = self.seed {
+            self.rng = StdRng::seed_from_u64(seed);
+        }
+        self.spawn_food();
+        if let Some(scenario) = self.scenario.take() {
+            self.apply_scenario(&scenario);
+            self.scenario = Some(scenario);
+        }
+    }
+
+    pub fn update(&mut self) -> GameEvent {
+        if self.state != GameState::Playing || self.awaiting_input {
+            return GameEvent::None;
+        }
+
+        // The eaten and wrap markers only last for the tick they happened
+        self.last_eaten = None;
+        self.last_wrap = None;
+
+        if self.respawn_countdown > 0 {
+            self.respawn_countdown -= 1;
+            if self.respawn_countdown == 0 {
+                self.spawn_food();
+            }
+        }
+
+        if self.shrink_interval > 0 {
+            self.ticks_until_shrink -= 1;
+            if self.ticks_until_shrink == 0 {
+                self.ticks_until_shrink = self.shrink_interval;
+                if self.shrink() {
+                    return self.crash();
+                }
+            }
+        }
+
+        let wrapping = self.wraps_towards(self.snake.direction);
+        self.grace_ticks_left = self.grace_ticks_left.saturating_sub(1);
+        let old_head = self.snake.head();
+
+        let moved = if wrapping {
+            Some(
+                self.snake
+                    .move_forward_with_wrapping(self.board_width, self.board_height),
+            )
+        } else {
+            self.snake.move_forward()
+        };
+        self.committed_direction = self.snake.direction;
+        // Off the top or left edge there's no cell to move to
+        let Some(old_tail) = moved else {
+            return self.crash();
+        };
+
+        let head = self.snake.head();
+        // A wrapped head ends up behind where it started along its heading
+        let wrapped = wrapping
+            && match self.snake.direction {
+                Direction::Up => head.y >= old_head.y,
+                Direction::Down => head.y <= old_head.y,
+                Direction::Left => head.x >= old_head.x,
+                Direction::Right => head.x <= old_head.x,
+            };
+        if wrapped {
+            self.last_wrap = Some(self.snake.direction);
+            self.wrap_count += 1;
+        }
+
+        // Check wall collision only if wrapping is disabled
+        if !wrapping && self.is_out_of_bounds(head) {
+            return self.crash();
+        }
+
+        // Peaceful mode keeps its promise of no deaths
+        let on_spike = !self.peaceful && self.spikes.contains(&head);
+        if on_spike || self.is_solid_obstacle(head) {
+            return self.crash();
+        }
+
+        // Check self collision
+        if self.self_collision && !self.peaceful && self.snake.collides_with_body(self.neck_grace) {
+            return self.crash();
+        }
+
+        // Check food collision
+        if self.food == Some(head) {
+            let mut value = self.food_kind.points();
+            // The first meal of a game has nothing to chain from
+            let combo = self.combo_window > 0
+                && self.food_eaten > 0
+                && self.ticks_since_food < self.combo_window;
+            if combo {
+                self.combo += 1;
+                value *= self.combo_multiplier;
+            } else {
+                self.combo = 0;
+            }
+            self.grow(old_tail);
+            self.score += value;
+            self.food_eaten += 1;
+            self.last_eaten = Some(head);
+            self.ticks_since_food = 0;
+
+            // The snake fills the whole board, so there's nowhere left for food
+            if self.snake.len() >= self.win_length() {
+                self.state = GameState::Won;
+                return GameEvent::Won;
+            }
+
+            self.food = None;
+            if self.respawn_delay == 0 {
+                self.spawn_food();
+            } else {
+                self.respawn_countdown = self.respawn_delay;
+            }
+            if self.big_food.is_none() && self.rng.random_bool(BIG_FOOD_CHANCE) {
+                self.spawn_big_food();
+            }
+            if self.power_up.is_none() && self.rng.random_bool(POWER_UP_CHANCE) {
+                self.spawn_power_up();
+            }
+            if combo {
+                return GameEvent::Combo(self.combo_multiplier);
+            }
+            return GameEvent::FoodEaten { value };
+        }
+
+        // Check power-up collision - power-ups don't grow the snake
+        if let Some((pos, kind)) = self.power_up {
+            if head == pos {
+                self.power_up = None;
+                self.starve();
+                self.restore_food();
+                return GameEvent::PowerUp(kind);
+            }
+        }
+
+        // Check big food collision - touching any of its cells counts
+        if let Some((_, size)) = self.big_food {
+            if self.is_big_food_cell(head) {
+                self.grow(old_tail);
+                self.score += FOOD_POINTS * u32::from(size) * u32::from(size);
+                self.food_eaten += 1;
+                self.big_food = None;
+                self.ticks_since_food = 0;
+                self.restore_food();
+                return GameEvent::BigFoodEaten;
+            }
+        }
+
+        self.starve();
+        if self.last_wrap.is_some() {
+            return GameEvent::Wrapped;
+        }
+        GameEvent::Moved
+    }
+
+    /// Advances up to `n` ticks, steering with `directions[i]` on tick `i`
+    /// and repeating the last direction once the slice runs out. An empty
+    /// slice keeps the current heading. Stops early once the game is no longer
+    /// playing, so the result holds one event per tick actually played.
+    pub fn run_steps(&mut self, n: usize, directions: &[Direction]) -> Vec<GameEvent> {
+        let mut events = Vec::with_capacity(n);
+        for i in 0..n {
+            if let Some(&direction) = directions.get(i).or(directions.last()) {
+                self.set_direction(direction);
+            }
+
+            let event = self.update();
+            events.push(event);
+            if matches!(
+                event,
+                GameEvent::None | GameEvent::GameOver | GameEvent::Won
+            ) {
+                break;
+            }
+        }
+        events
+    }
+
+    /// Whether the food can no longer be reached from the head, treating the
+    /// snake's body, the walls, spikes and solid obstacles as blocked, just
+    /// like moving into them would be fatal. Wrapping edges connect to the
+    /// opposite side when wall wrapping is active.
+    pub fn is_trapped(&self) -> bool {
+        // Nothing to reach while the food is respawning
+        let Some(food) = self.food else {
+            return false;
+        };
+        let width = usize::from(self.board_width);
+        let index = |pos: Position| usize::from(pos.y) * width + usize::from(pos.x);
+
+        let mut blocked = vec![false; width * usize::from(self.board_height)];
+        for &segment in self.snake.body().iter().skip(1) {
+            if !self.is_out_of_bounds(segment) {
+                blocked[index(segment)] = true;
+            }
+        }
+        let hazards = self.spikes.iter().filter(|_| !self.peaceful).chain(
+            self.obstacles
+                .iter()
+                .filter(|&&pos| self.is_solid_obstacle(pos)),
+        );
+        for &pos in hazards {
+            if !self.is_out_of_bounds(pos) {
+                blocked[index(pos)] = true;
+            }
+        }
+
+        let head = self.snake.head();
+        if self.is_out_of_bounds(head) {
+            return true;
+        }
+
+        // Flood fill outwards from the head until the food turns up
+        let mut visited = blocked;
+        visited[index(head)] = true;
+        let mut queue = VecDeque::from([head]);
+        while let Some(pos) = queue.pop_front() {
+            if pos == food {
+                return false;
+            }
+            for direction in [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ] {
+                if let Some(next) = self.neighbor(pos, direction) {
+                    if !visited[index(next)] {
+                        visited[index(next)] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Cell the head moves into on the next tick, or `None` if that move
+    /// would end the game.
+    pub fn next_head(&self) -> Option<Position> {
+        self.head_after(self.snake.direction)
+    }
+
+    /// Cell the head would move into next tick heading `direction`, or
+    /// `None` if that move would end the game.
+    pub fn head_after(&self, direction: Direction) -> Option<Position> {
+        let next = self.neighbor(self.snake.head(), direction)?;
+        // The tail moves out of the way, so it doesn't count
+        let body = self.snake.body();
+        let neck = if self.neck_grace { Some(1) } else { None };
+        let hits_body = body
+            .iter()
+            .enumerate()
+            .take(body.len() - 1)
+            .any(|(index, &pos)| pos == next && Some(index) != neck);
+        let fatal = (hits_body && self.self_collision && !self.peaceful)
+            || (!self.peaceful && self.spikes.contains(&next))
+            || self.is_solid_obstacle(next);
+        (!fatal).then_some(next)
+    }
+
+    /// Cell next to `pos` in `direction`, or `None` if that's a wall. Edges
+    /// wrap exactly as they would for the snake this tick.
+    pub fn neighbor(&self, pos: Position, direction: Direction) -> Option<Position> {
+        let wrap = if self.wraps_towards(direction) {
+            WrapEdges::ALL
+        } else {
+            WrapEdges::NONE
+        };
+        let next = wrapped_position(pos, direction, self.board_width, self.board_height, wrap)?;
+        (!self.is_out_of_bounds(next)).then_some(next)
+    }
+
+    /// Copies the board, score and state into a `GameSnapshot`.
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            board_width: self.board_width,
+            board_height: self.board_height,
+            snake: self.snake.body().iter().copied().collect(),
+            direction: self.snake.direction,
+            food: self.food,
+            score: self.score,
+            state: self.state,
+        }
+    }
+
+    /// Plain text picture of the board using the same glyphs as the TUI, one
+    /// character per cell, below a header line with the score and seed.
+    pub fn to_text_grid(&self) -> String {
+        let width = usize::from(self.board_width);
+        let height = usize::from(self.board_height);
+        let mut cells = vec!['·'; width * height];
+        let mut put = |pos: Position, glyph: char| {
+            if pos.x < self.board_width && pos.y < self.board_height {
+                cells[usize::from(pos.y) * width + usize::from(pos.x)] = glyph;
+            }
+        };
+
+        // Layered like the renderer so later glyphs cover earlier ones
+        for y in 0..self.board_height {
+            for x in 0..self.board_width {
+                let pos = Position::new(x, y);
+                if self.is_out_of_bounds(pos) {
+                    put(pos, '░');
+                }
+            }
+        }
+        let obstacle_glyph = if self.solid_obstacles { '▓' } else { '▒' };
+        for &obstacle in &self.obstacles {
+            put(obstacle, obstacle_glyph);
+        }
+        for &spike in &self.spikes {
+            put(spike, '✖');
+        }
+        for (i, &segment) in self.snake.body().iter().enumerate() {
+            put(segment, if i == 0 { '●' } else { '○' });
+        }
+        if let Some((top_left, size)) = self.big_food {
+            for dy in 0..size {
+                for dx in 0..size {
+                    put(Position::new(top_left.x + dx, top_left.y + dy), '◆');
+                }
+            }
+        }
+        if let Some((pos, kind)) = self.power_up {
+            let glyph = match kind {
+                PowerUpKind::Slow => '▼',
+                PowerUpKind::Fast => '▲',
+            };
+            put(pos, glyph);
+        }
+        if let Some(food) = self.food {
+            put(
+                food,
+                match self.food_kind {
+                    FoodKind::Normal => '◆',
+                    FoodKind::Golden => '★',
+                },
+            );
+        }
+
+        let seed = self
+            .seed
+            .map_or_else(|| String::from("none"), |seed| seed.to_string());
+        let mut text = format!("Score: {} Seed: {}\n", self.score, seed);
+        for row in cells.chunks(width.max(1)) {
+            text.extend(row);
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Handles a fatal collision: uses up a life and starts a fresh snake in
+    /// the middle of the board, or ends the game on the last life.
+    fn crash(&mut self) -> GameEvent {
+        if self.respawn_on_death && self.teleport() {
+            self.score = self.score.saturating_sub(RESPAWN_PENALTY);
+            return GameEvent::Respawned;
+        }
+
+        self.lives = self.lives.saturating_sub(1);
+        if self.lives == 0 {
+            self.state = GameState::GameOver;
+            return GameEvent::GameOver;
+        }
+
+        // Respawn shorter, so a lost life also costs length
+        self.snake = initial_snake(
+            self.board_width,
+            self.board_height,
+            self.board_height / 2,
+            self.initial_direction,
+            RESPAWN_LENGTH,
+        );
+        // A shrunken board may not fit even the shorter snake any more
+        if self
+            .snake
+            .body()
+            .iter()
+            .any(|&pos| self.is_out_of_bounds(pos))
+        {
+            let center = Position::new(self.board_width / 2, self.board_height / 2);
+            self.snake = Snake::with_body(vec![center], self.initial_direction);
+        }
+        self.committed_direction = self.snake.direction;
+        self.grace_ticks_left = self.wall_grace_ticks;
+        if self
+            .food
+            .is_some_and(|food| self.snake.body().contains(&food))
+        {
+            self.spawn_food();
+        }
+        GameEvent::LifeLost
+    }
+
+    /// Replaces the snake with a single segment on a random free cell,
+    /// heading somewhere it can move. Returns `false`, leaving the snake
+    /// alone, if no cell is free.
+    fn teleport(&mut self) -> bool {
+        let cells: Vec<Position> = self
+            .free_cells()
+            .filter(|&pos| self.food != Some(pos))
+            .collect();
+        if cells.is_empty() {
+            return false;
+        }
+        let cell = cells[self.rng.random_range(0..cells.len())];
+
+        // Keep the heading if it's clear, so the player isn't surprised
+        let current = self.snake.direction;
+        let direction = [
+            current,
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+        .into_iter()
+        .find(|&direction| {
+            self.neighbor(cell, direction)
+                .is_some_and(|next| !self.is_blocked(next) && !self.is_big_food_cell(next))
+        })
+        .unwrap_or(current);
+
+        self.snake = Snake::with_body(vec![cell], direction);
+        self.committed_direction = direction;
+        self.grace_ticks_left = self.wall_grace_ticks;
+        true
+    }
+
+    /// Puts the tail dropped this tick back, unless the snake is already as
+    /// long as it may get.
+    fn grow(&mut self, old_tail: Position) {
+        if self.max_length.is_none_or(|max| self.snake.len() < max) {
+            self.snake.grow(old_tail);
+        }
+    }
+
+    /// Counts a tick without food and applies hunger when it's due.
+    fn starve(&mut self) {
+        self.ticks_since_food = self.ticks_since_food.saturating_add(1);
+        if let Some(rate) = self.hunger_rate {
+            if self.ticks_since_food.is_multiple_of(rate) {
+                self.score = self.score.saturating_sub(1);
+            }
+        }
+    }
+
+    fn is_out_of_bounds(&self, pos: Position) -> bool {
+        let inset = self.shrink_inset;
+        let closed = pos.x < inset
+            || pos.y < inset
+            || pos.x >= self.board_width.saturating_sub(inset)
+            || pos.y >= self.board_height.saturating_sub(inset);
+        closed
+            || !self
+                .board_shape
+                .contains(pos, self.board_width, self.board_height)
+    }
+
+    /// Walls off the next ring of cells unless the board is already down to
+    /// its smallest size. Returns whether that caught the snake.
+    fn shrink(&mut self) -> bool {
+        let inset = self.shrink_inset + 1;
+        let fits = |size: u16| size.saturating_sub(2 * inset) >= MIN_SHRUNK_SIZE;
+        if !fits(self.board_width) || !fits(self.board_height) {
+            return false;
+        }
+        self.shrink_inset = inset;
+
+        if self
+            .snake
+            .body()
+            .iter()
+            .any(|&pos| self.is_out_of_bounds(pos))
+        {
+            return true;
+        }
+        if self.food.is_some_and(|food| self.is_out_of_bounds(food)) {
+            self.spawn_food();
+        }
+        if self.big_food.is_some_and(|(top_left, size)| {
+            self.is_out_of_bounds(top_left)
+                || self
+                    .is_out_of_bounds(Position::new(top_left.x + size - 1, top_left.y + size - 1))
+        }) {
+            self.big_food = None;
+        }
+        if self
+            .power_up
+            .is_some_and(|(pos, _)| self.is_out_of_bounds(pos))
+        {
+            self.power_up = None;
+        }
+        false
+    }
+
+    /// Snake length that wins the game by filling the board.
+    pub fn win_length(&self) -> usize {
+        let blocked = self
+            .board_cells()
+            .filter(|&pos| !self.is_out_of_bounds(pos) && self.is_blocked(pos))
+            .count();
+        self.playable_cells() - blocked
+    }
+
+    /// Empty cells left on the current board: inside its bounds and free of
+    /// the snake, obstacles, spikes, big food and power-ups.
+    pub fn available_cells(&self) -> usize {
+        self.free_cells().count()
+    }
+
+    /// Cells inside the board that aren't blocked or covered by the snake,
+    /// big food or a power-up.
+    fn free_cells(&self) -> impl Iterator<Item = Position> + '_ {
+        self.board_cells().filter(|&pos| {
+            !self.is_out_of_bounds(pos)
+                && !self.is_blocked(pos)
+                && !self.snake.body().contains(&pos)
+                && !self.is_big_food_cell(pos)
+                && self.power_up.is_none_or(|(power_up, _)| power_up != pos)
+        })
+    }
+
+    /// Whether an obstacle or spike keeps food and the win count off `pos`.
+    fn is_blocked(&self, pos: Position) -> bool {
+        self.obstacles.contains(&pos) || self.spikes.contains(&pos)
+    }
+
+    /// Every cell of the board, row by row.
+    fn board_cells(&self) -> impl Iterator<Item = Position> + use<> {
+        let width = self.board_width;
+        (0..self.board_height).flat_map(move |y| (0..width).map(move |x| Position::new(x, y)))
+    }
+
+    /// Number of cells inside the board shape.
+    fn playable_cells(&self) -> usize {
+        self.board_cells()
+            .filter(|&pos| !self.is_out_of_bounds(pos))
+            .count()
+    }
+
+    /// Moves the food to a random free cell. The game does this itself
+    /// whenever food is eaten.
+    pub fn spawn_food(&mut self) {
+        let mut cells: Vec<Position> = self.free_cells().collect();
+        // Nowhere left to put food, e.g. a snake filling the board
+        if cells.is_empty() {
+            return;
+        }
+        if self.food_avoids_next_head && cells.len() > 1 {
+            let ahead = self.neighbor(self.snake.head(), self.snake.direction);
+            cells.retain(|&pos| Some(pos) != ahead);
+        }
+        self.food = Some(cells[self.rng.random_range(0..cells.len())]);
+
+        self.food_kind = if self.rng.random_bool(self.golden_food_chance) {
+            FoodKind::Golden
+        } else {
+            FoodKind::Normal
+        };
+    }
+
+    /// Spawns food if the last spawn found no room and none is on its way,
+    /// now that eating a power-up or big food freed up cells.
+    fn restore_food(&mut self) {
+        if self.food.is_none() && self.respawn_countdown == 0 {
+            self.spawn_food();
+        }
+    }
+
+    fn spawn_power_up(&mut self) {
+        let kind = if self.rng.random_bool(0.5) {
+            PowerUpKind::Slow
+        } else {
+            PowerUpKind::Fast
+        };
+
+        for _ in 0..POWER_UP_SPAWN_ATTEMPTS {
+            let x = self.rng.random_range(0..self.board_width);
+            let y = self.rng.random_range(0..self.board_height);
+            let pos = Position::new(x, y);
+
+            if self.food != Some(pos)
+                && !self.is_out_of_bounds(pos)
+                && !self.is_blocked(pos)
+                && !self.snake.body().contains(&pos)
+                && !self.is_big_food_cell(pos)
+            {
+                self.power_up = Some((pos, kind));
+                return;
+            }
+        }
+    }
+
+    fn is_big_food_cell(&self, pos: Position) -> bool {
+        match self.big_food {
+            Some((top_left, size)) => {
+                pos.x >= top_left.x
+                    && pos.x < top_left.x + size
+                    && pos.y >= top_left.y
+                    && pos.y < top_left.y + size
+            }
+            None => false,
+        }
+    }
+
+    fn spawn_big_food(&mut self) {
+        let size = BIG_FOOD_SIZE;
+
+        // Skip on boards too small to leave room around the big food
+        if self.board_width < BIG_FOOD_MIN_BOARD || self.board_height < BIG_FOOD_MIN_BOARD {
+            return;
+        }
+
+        for _ in 0..BIG_FOOD_SPAWN_ATTEMPTS {
+            let x = self.rng.random_range(0..=self.board_width - size);
+            let y = self.rng.random_range(0..=self.board_height - size);
+            let top_left = Position::new(x, y);
+
+            let overlaps = (0..size).any(|dy| {
+                (0..size).any(|dx| {
+                    let cell = Position::new(x + dx, y + dy);
+                    self.food == Some(cell)
+                        || self.is_out_of_bounds(cell)
+                        || self.is_blocked(cell)
+                        || self.snake.body().contains(&cell)
+                        || self.power_up.is_some_and(|(pos, _)| pos == cell)
+                })
+            });
+
+            if !overlaps {
+                self.big_food = Some((top_left, size));
+                return;
+            }
+        }
+    }
+}
+
+/// Starting snake on row `y`, heading right with its body extending left from
+/// the head so the whole body is on the board. The snake is `length`
+/// segments long, but at least one, and boards too narrow for that get a
+/// shorter snake.
+fn initial_snake(
+    board_width: u16,
+    board_height: u16,
+    y: u16,
+    direction: Direction,
+    length: u16,
+) -> Snake {
+    let x = board_width / 2;
+    let body = match direction {
+        Direction::Right | Direction::Left => {
+            let length = length.min(board_width).max(1);
+            if direction == Direction::Right {
+                let head_x = (x + length - 1).min(board_width.saturating_sub(1));
+                (0..length).map(|i| Position::new(head_x - i, y)).collect()
+            } else {
+                let head_x = x.min(board_width.saturating_sub(length));
+                (0..length).map(|i| Position::new(head_x + i, y)).collect()
+            }
+        }
+        Direction::Down | Direction::Up => {
+            let length = length.min(board_height).max(1);
+            if direction == Direction::Down {
+                let head_y = (y + length - 1).min(board_height.saturating_sub(1));
+                (0..length).map(|i| Position::new(x, head_y - i)).collect()
+            } else {
+                let head_y = y.min(board_height.saturating_sub(length));
+                (0..length).map(|i| Position::new(x, head_y + i)).collect()
+            }
+        }
+    };
+    Snake::with_body(body, direction)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEvent {
+    None,
+    Moved,
+    /// Moved across an edge to the opposite side of the board.
+    Wrapped,
+    FoodEaten {
+        value: u32,
+    },
+    BigFoodEaten,
+    /// Ate food soon enough after the last meal to score `multiplier` times
+    /// its points.
+    Combo(u32),
+    PowerUp(PowerUpKind),
+    /// Crashed, but a life was left so a new snake took its place.
+    LifeLost,
+    /// Crashed and was teleported to a free cell instead of dying.
+    Respawned,
+    GameOver,
+    Won,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Curls the snake into a U-shape heading down, so the next move runs
+    /// into its own body.
+    fn curl_into_self(game: &mut Game) {
+        game.snake.body = VecDeque::from(vec![
+            Position::new(2, 2),
+            Position::new(1, 2),
+            Position::new(1, 3),
+            Position::new(2, 3),
+            Position::new(3, 3),
+        ]);
+        game.snake.direction = Direction::Down;
+    }
+
+    #[test]
+    fn test_dimension_getters_match_construction() {
+        let game = Game::with_seed(17, 9, 1);
+
+        assert_eq!(game.board_width(), 17);
+        assert_eq!(game.board_height(), 9);
+        assert_eq!(game.dimensions(), (17, 9));
+    }
+
+    #[test]
+    fn test_initial_body_fits_small_board() {
+        let mut game = Game::with_seed(3, 2, 1);
+        let in_bounds = |game: &Game| {
+            game.snake()
+                .body()
+                .iter()
+                .all(|&pos| !game.is_out_of_bounds(pos))
+        };
+
+        assert_eq!(game.snake().len(), 3);
+        assert!(in_bounds(&game));
+        assert!(!game.snake().check_self_collision());
+
+        game.reset();
+        assert_eq!(game.snake().len(), 3);
+        assert!(in_bounds(&game));
+
+        let roomy = Game::with_seed(20, 10, 1);
+        assert_eq!(roomy.snake().len(), usize::from(INITIAL_SNAKE_LENGTH));
+        assert!(in_bounds(&roomy));
+    }
+
+    #[test]
+    fn test_wall_wrapping_horizontal() {
+        let mut game = Game::new(5, 5);
+        game.set_wall_wrapping(true);
+
+        // Position snake at the right edge
+        game.snake.body.clear();
+        game.snake.body.push_back(Position::new(4, 2));
+        game.snake.set_direction(Direction::Right);
+
+        // Move forward - should wrap to left side
+        game.update();
+
+        assert_eq!(game.snake.head().x, 0);
+        assert_eq!(game.snake.head().y, 2);
+        assert_eq!(game.state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_crossing_edge_emits_wrapped() {
+        let mut game = Game::with_seed(10, 5, 4);
+        game.set_wall_wrapping(true);
+        game.snake.body = VecDeque::from(vec![Position::new(9, 2)]);
+        game.snake.direction = Direction::Right;
+        game.food = Some(Position::new(5, 4));
+
+        assert_eq!(game.update(), GameEvent::Wrapped);
+        assert_eq!(game.last_wrap(), Some(Direction::Right));
+
+        assert_eq!(game.update(), GameEvent::Moved);
+        assert_eq!(game.last_wrap(), None);
+    }
+
+    #[test]
+    fn test_solid_top_wall_is_not_a_wrap() {
+        let mut game = Game::with_seed(10, 5, 4);
+        game.snake.body = VecDeque::from(vec![Position::new(2, 0)]);
+        game.snake.direction = Direction::Up;
+
+        assert_ne!(game.update(), GameEvent::Wrapped);
+        assert_eq!(game.last_wrap(), None);
+    }
+
+    #[test]
+    fn test_wrap_count_only_counts_wraps() {
+        let mut game = Game::with_seed(5, 5, 4);
+        game.set_wall_wrapping(true);
+        game.snake.body = VecDeque::from(vec![Position::new(0, 0)]);
+        game.snake.direction = Direction::Right;
+        game.food = Some(Position::new(4, 4));
+
+        // Two trips across the board cross the right edge twice
+        game.run_steps(10, &[Direction::Right]);
+        assert_eq!(game.wrap_count(), 2);
+
+        // Moving down within the board doesn't wrap
+        game.run_steps(3, &[Direction::Down]);
+        assert_eq!(game.wrap_count(), 2);
+
+        game.reset();
+        assert_eq!(game.wrap_count(), 0);
+    }
+
+    #[test]
+    fn test_next_head_follows_direction_and_avoids_collisions() {
+        let mut game = Game::with_seed(10, 10, 2);
+        game.snake.body = VecDeque::from(vec![Position::new(9, 4), Position::new(8, 4)]);
+        game.snake.direction = Direction::Up;
+        assert_eq!(game.next_head(), Some(Position::new(9, 3)));
+
+        // Into the wall, unless it wraps
+        game.snake.direction = Direction::Right;
+        assert_eq!(game.next_head(), None);
+        game.set_wall_wrapping(true);
+        assert_eq!(game.next_head(), Some(Position::new(0, 4)));
+
+        game.snake.body = VecDeque::from(vec![
+            Position::new(2, 2),
+            Position::new(2, 3),
+            Position::new(3, 3),
+            Position::new(3, 2),
+            Position::new(3, 1),
+        ]);
+        assert_eq!(game.next_head(), None);
+    }
+
+    #[test]
+    fn test_board_shrinks_after_interval() {
+        let mut game = Game::with_seed(20, 20, 5);
+        game.set_shrink_interval(3);
+        // Keep the snake well inside so the shrinking doesn't catch it
+        game.snake = Snake::with_body(vec![Position::new(10, 10)], Direction::Right);
+        game.food = Some(Position::new(10, 15));
+        let edge = Position::new(0, 10);
+        assert!(game.is_playable(edge));
+
+        game.update();
+        game.update();
+        assert_eq!(game.shrink_inset(), 0);
+
+        game.update();
+        assert_eq!(game.shrink_inset(), 1);
+        assert!(!game.is_playable(edge));
+        assert!(game.is_playable(Position::new(1, 10)));
+        assert_eq!(game.state(), GameState::Playing);
+    }
+
+    #[test]
+    fn test_shrinking_onto_the_snake_is_fatal() {
+        let mut game = Game::with_seed(20, 20, 5);
+        game.set_shrink_interval(1);
+        game.snake = Snake::with_body(vec![Position::new(10, 0)], Direction::Left);
+        game.food = Some(Position::new(10, 15));
+
+        assert_eq!(game.update(), GameEvent::GameOver);
+    }
+
+    #[test]
+    fn test_crash_with_lives_left_respawns() {
+        let mut game = Game::with_seed(20, 10, 8);
+        game.set_lives(2);
+        game.snake = Snake::with_body(vec![Position::new(19, 3)], Direction::Right);
+        game.food = Some(Position::new(0, 0));
+        game.score = 30;
+
+        assert_eq!(game.update(), GameEvent::LifeLost);
+        assert_eq!(game.state(), GameState::Playing);
+        assert_eq!(game.lives(), 1);
+        assert_eq!(game.score(), 30);
+        assert_eq!(game.food(), Some(Position::new(0, 0)));
+        assert_eq!(game.snake().len(), 2);
+        assert!(game.is_playable(game.snake().head()));
+
+        // The last life ends the game
+        game.snake = Snake::with_body(vec![Position::new(19, 3)], Direction::Right);
+        assert_eq!(game.update(), GameEvent::GameOver);
+        assert_eq!(game.lives(), 0);
+
+        game.reset();
+        assert_eq!(game.lives(), 2);
+    }
+
+    #[test]
+    fn test_wall_wrapping_vertical() {
+        let mut game = Game::new(5, 5);
+        game.set_wall_wrapping(true);
+
+        // Position snake at the top edge
+        game.snake.body.clear();
+        game.snake.body.push_back(Position::new(2, 0));
+        game.snake.set_direction(Direction::Up);
+
+        // Move forward - should wrap to bottom side
+        game.update();
+
+        assert_eq!(game.snake.head().x, 2);
+        assert_eq!(game.snake.head().y, 4);
+        assert_eq!(game.state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_no_wall_wrapping() {
+        let mut game = Game::new(5, 5);
+
+        // Position snake at the right edge
+        game.snake.body.clear();
+        game.snake.body.push_back(Position::new(4, 2));
+        game.snake.set_direction(Direction::Right);
+
+        // Move forward - should cause game over
+        game.update();
+
+        assert_eq!(game.state, GameState::GameOver);
+    }
+
+    #[test]
+    fn test_walls_wrap_once_score_threshold_is_reached() {
+        let mut game = Game::with_seed(5, 5, 8);
+        game.set_wall_wrapping(true);
+        game.set_wrap_after_score(Some(20));
+        game.food = Some(Position::new(0, 4));
+
+        // Below the threshold the wall is solid despite wall wrapping
+        game.score = 10;
+        game.snake.body = VecDeque::from(vec![Position::new(4, 2)]);
+        game.snake.direction = Direction::Right;
+        assert_eq!(game.update(), GameEvent::GameOver);
+
+        game.reset();
+        game.food = Some(Position::new(0, 4));
+        game.score = 20;
+        game.snake.body = VecDeque::from(vec![Position::new(4, 2)]);
+        game.snake.direction = Direction::Right;
+        assert_eq!(game.update(), GameEvent::Wrapped);
+        assert_eq!(game.snake().head(), Position::new(0, 2));
+    }
+
+    #[test]
+    fn test_big_food_any_cell_grants_larger_score() {
+        let mut game = Game::new(20, 20);
+        game.food = Some(Position::new(0, 0));
+        game.big_food = Some((Position::new(10, 10), 2));
+
+        // Approach the bottom-right cell of the big food from the right
+        game.snake.body.clear();
+        game.snake.body.push_back(Position::new(12, 11));
+        game.snake.direction = Direction::Left;
+
+        let event = game.update();
+
+        assert_eq!(event, GameEvent::BigFoodEaten);
+        assert_eq!(game.score(), 40);
+        assert_eq!(game.big_food(), None);
+        assert_eq!(game.snake.len(), 2);
+    }
+
+    #[test]
+    fn test_seeded_games_place_food_identically() {
+        let first = Game::with_seed(30, 20, 42);
+        let second = Game::with_seed(30, 20, 42);
+
+        assert_eq!(first.food(), second.food());
+        assert_eq!(first.seed(), Some(42));
+    }
+
+    #[test]
+    fn test_peaceful_mode_survives_self_collision() {
+        let mut game = Game::new(10, 10);
+        game.set_peaceful(true);
+        game.food = Some(Position::new(9, 9));
+
+        curl_into_self(&mut game);
 
-        // Move forward - should cause game over
         game.update();
 
-        assert_eq!(game.state, GameState::GameOver);
+        assert!(game.snake.check_self_collision());
+        assert_eq!(game.state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_neck_grace_forgives_doubling_back() {
+        let reversing_game = |neck_grace| {
+            let mut game = Game::with_seed(10, 10, 4);
+            game.set_neck_grace(neck_grace);
+            game.food = Some(Position::new(9, 9));
+            game.snake.body = VecDeque::from(vec![
+                Position::new(4, 2),
+                Position::new(3, 2),
+                Position::new(2, 2),
+            ]);
+            // Bypasses the reversal guard, like a stale buffered turn would
+            game.snake.direction = Direction::Left;
+            game
+        };
+
+        let mut classic = reversing_game(false);
+        assert_eq!(classic.next_head(), None);
+        assert_eq!(classic.update(), GameEvent::GameOver);
+
+        let mut forgiving = reversing_game(true);
+        assert_eq!(forgiving.next_head(), Some(Position::new(3, 2)));
+        assert_eq!(forgiving.update(), GameEvent::Moved);
+        assert_eq!(forgiving.snake().head(), Position::new(3, 2));
+
+        // The rest of the body still counts
+        forgiving.snake.body = VecDeque::from(vec![
+            Position::new(2, 2),
+            Position::new(2, 3),
+            Position::new(3, 3),
+            Position::new(3, 2),
+            Position::new(3, 1),
+        ]);
+        forgiving.snake.direction = Direction::Right;
+        assert_eq!(forgiving.update(), GameEvent::GameOver);
+    }
+
+    #[test]
+    fn test_disabled_self_collision_keeps_playing() {
+        let mut game = Game::with_seed(10, 10, 4);
+        game.set_self_collision(false);
+        game.food = Some(Position::new(9, 9));
+        curl_into_self(&mut game);
+
+        assert_eq!(game.update(), GameEvent::Moved);
+        assert!(game.snake.check_self_collision());
+        assert_eq!(game.state(), GameState::Playing);
+    }
+
+    #[test]
+    fn test_from_config_applies_settings() {
+        let config = GameConfig::new(25, 12)
+            .with_wall_wrapping(false)
+            .with_seed(7);
+
+        let game = Game::from_config(&config);
+
+        assert_eq!(game.board_width, 25);
+        assert_eq!(game.board_height, 12);
+        assert!(!game.wall_wrapping());
+        assert_eq!(game.seed(), Some(7));
+
+        let wrapping = Game::from_config(&GameConfig::new(25, 12).with_wall_wrapping(true));
+        assert!(wrapping.wall_wrapping());
+    }
+
+    #[test]
+    fn test_filling_the_board_wins() {
+        let mut game = Game::new(3, 1);
+        game.snake.body = VecDeque::from(vec![Position::new(1, 0), Position::new(0, 0)]);
+        game.snake.direction = Direction::Right;
+        game.food = Some(Position::new(2, 0));
+
+        assert_eq!(game.update(), GameEvent::Won);
+        assert_eq!(game.state(), GameState::Won);
+    }
+
+    #[test]
+    fn test_circle_board_corners_are_out_of_bounds() {
+        let mut game = Game::with_seed(20, 20, 1);
+        game.set_board_shape(BoardShape::Circle);
+
+        assert!(game.is_out_of_bounds(Position::new(0, 0)));
+        assert!(game.is_out_of_bounds(Position::new(19, 1)));
+        assert!(!game.is_out_of_bounds(Position::new(10, 10)));
+        assert!(!game.is_out_of_bounds(game.food().unwrap()));
+    }
+
+    #[test]
+    fn test_only_enabled_edges_wrap() {
+        let mut game = Game::with_seed(10, 10, 3);
+        game.set_wall_wrapping(true);
+        game.set_wrap_edges(WrapEdges {
+            top: false,
+            bottom: false,
+            left: false,
+            right: true,
+        });
+        game.food = None;
+        game.big_food = None;
+        game.power_up = None;
+
+        game.snake = Snake::with_body(vec![Position::new(9, 4)], Direction::Right);
+        assert_eq!(game.update(), GameEvent::Wrapped);
+        assert_eq!(game.snake().head(), Position::new(0, 4));
+
+        game.snake = Snake::with_body(vec![Position::new(0, 4)], Direction::Left);
+        game.committed_direction = Direction::Left;
+        assert_eq!(game.update(), GameEvent::GameOver);
+    }
+
+    #[test]
+    fn test_walls_wrap_during_grace_ticks() {
+        let mut game = Game::with_seed(10, 10, 3);
+        game.set_wall_wrapping(false);
+        game.set_wall_grace_ticks(2);
+        game.food = None;
+        game.big_food = None;
+        game.power_up = None;
+
+        game.snake = Snake::with_body(vec![Position::new(9, 4)], Direction::Right);
+        assert_eq!(game.update(), GameEvent::Wrapped);
+        assert_eq!(game.snake().head(), Position::new(0, 4));
+
+        // The second grace tick is used up before reaching the wall
+        game.snake = Snake::with_body(vec![Position::new(8, 4)], Direction::Right);
+        assert_eq!(game.update(), GameEvent::Moved);
+        assert_eq!(game.update(), GameEvent::GameOver);
+    }
+
+    #[test]
+    fn test_wait_for_input_holds_snake_until_direction() {
+        let config = GameConfig::new(20, 15).with_wait_for_input(true);
+        let mut game = Game::from_config(&config);
+        let start = game.snake().head();
+
+        assert_eq!(game.update(), GameEvent::None);
+        game.toggle_pause();
+        game.toggle_pause();
+        assert_eq!(game.update(), GameEvent::None);
+        assert_eq!(game.snake().head(), start);
+
+        game.set_direction(Direction::Down);
+        game.update();
+        assert_eq!(game.snake().head(), Position::new(start.x, start.y + 1));
+
+        game.reset();
+        assert!(game.is_awaiting_input());
+    }
+
+    #[test]
+    fn test_scenario_places_snake_and_food() {
+        let path = std::env::temp_dir().join(format!(
+            "snake_rs_scenario_test_{}.json",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            r#"{
+                "board_width": 12,
+                "board_height": 8,
+                "body": [{ "x": 4, "y": 2 }, { "x": 4, "y": 3 }, { "x": 5, "y": 3 }],
+                "direction": "Up",
+                "food": { "x": 4, "y": 0 },
+                "score": 70
+            }"#,
+        )
+        .unwrap();
+
+        let scenario = Scenario::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let mut game = Game::from_scenario(&scenario).unwrap();
+
+        let body: Vec<Position> = game.snake().body().iter().copied().collect();
+        assert_eq!(body, scenario.body);
+        assert_eq!(game.snake().direction(), Direction::Up);
+        assert_eq!(game.food(), Some(Position::new(4, 0)));
+        assert_eq!(game.score(), 70);
+        assert_eq!(game.dimensions(), (12, 8));
+
+        // Restarting goes back to the scenario
+        game.update();
+        game.reset();
+        assert_eq!(game.snake().head(), Position::new(4, 2));
+    }
+
+    #[test]
+    fn test_eating_at_max_length_scores_without_growing() {
+        let mut game = Game::with_seed(20, 10, 4);
+        game.set_max_length(Some(5));
+        game.big_food = None;
+        game.power_up = None;
+        let head = game.snake().head();
+        game.food = Some(Position::new(head.x + 1, head.y));
+        game.food_kind = FoodKind::Normal;
+
+        assert!(matches!(game.update(), GameEvent::FoodEaten { .. }));
+        assert_eq!(game.score(), FOOD_POINTS);
+        assert_eq!(game.snake().len(), 5);
+    }
+
+    #[test]
+    fn test_snapshot_is_detached_from_the_game() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let mut game = Game::with_seed(20, 15, 8);
+        let snapshot = game.snapshot();
+        assert_send_sync(&snapshot);
+
+        assert_eq!(snapshot.snake[0], game.snake().head());
+        assert_eq!(snapshot.food, game.food());
+        assert_eq!(snapshot.score, game.score());
+
+        let head = game.snake().head();
+        game.update();
+        game.score = 100;
+        assert_eq!(snapshot.snake[0], head);
+        assert_eq!(snapshot.score, 0);
+        assert_ne!(snapshot, game.snapshot());
+    }
+
+    #[test]
+    fn test_solid_obstacle_is_fatal_even_when_wrapping() {
+        let mut game = Game::with_seed(20, 10, 2);
+        game.set_wall_wrapping(true);
+        let head = game.snake().head();
+        game.set_obstacles(vec![Position::new(head.x + 1, head.y)]);
+
+        assert_eq!(game.update(), GameEvent::GameOver);
+    }
+
+    #[test]
+    fn test_soft_obstacle_lets_snake_pass() {
+        let mut game = Game::with_seed(20, 10, 2);
+        let head = game.snake().head();
+        let obstacle = Position::new(head.x + 1, head.y);
+        game.set_obstacles(vec![obstacle]);
+        game.set_solid_obstacles(false);
+        game.food = None;
+
+        game.update();
+        assert_eq!(game.state(), GameState::Playing);
+        assert_eq!(game.snake().head(), obstacle);
+
+        // Food still never lands on it
+        for _ in 0..50 {
+            game.spawn_food();
+            assert_ne!(game.food(), Some(obstacle));
+        }
+    }
+
+    #[test]
+    fn test_available_cells_excludes_snake_and_obstacles() {
+        let mut game = Game::with_seed(6, 4, 1);
+        game.snake = Snake::with_body(
+            vec![
+                Position::new(2, 1),
+                Position::new(1, 1),
+                Position::new(0, 1),
+            ],
+            Direction::Right,
+        );
+        assert_eq!(game.available_cells(), 24 - 3);
+
+        // Obstacles under the snake or off the board don't count twice
+        game.set_obstacles(vec![
+            Position::new(5, 3),
+            Position::new(4, 0),
+            Position::new(1, 1),
+            Position::new(9, 9),
+        ]);
+        assert_eq!(game.available_cells(), 24 - 3 - 2);
+        assert_eq!(game.win_length(), 24 - 3);
+    }
+
+    #[test]
+    fn test_food_spawn_skips_board_left_to_a_power_up() {
+        let mut game = Game::with_seed(4, 1, 1);
+        game.snake = Snake::with_body(
+            vec![Position::new(1, 0), Position::new(0, 0)],
+            Direction::Right,
+        );
+        game.food = Some(Position::new(2, 0));
+        game.place_power_up(Position::new(3, 0), PowerUpKind::Slow);
+
+        // The power-up covers the only cell left, so there's nowhere for food
+        assert!(matches!(game.update(), GameEvent::FoodEaten { .. }));
+        assert_eq!(game.available_cells(), 0);
+        assert_eq!(game.food(), None);
+        assert_eq!(game.state(), GameState::Playing);
+
+        assert_eq!(game.update(), GameEvent::PowerUp(PowerUpKind::Slow));
+        assert_eq!(game.food(), Some(Position::new(0, 0)));
+    }
+
+    #[test]
+    fn test_spike_is_fatal_but_food_spawns_beside_it() {
+        let mut game = Game::with_seed(5, 5, 7);
+        game.set_wall_wrapping(true);
+        let spike = Position::new(2, 2);
+        game.set_spikes(vec![spike]);
+
+        // Every cell but the spike and the one above it is taken
+        game.snake = Snake::with_body(vec![Position::new(0, 0)], Direction::Right);
+        let free = Position::new(2, 1);
+        game.set_obstacles(
+            game.board_cells()
+                .filter(|&pos| pos != spike && pos != free && pos != Position::new(0, 0))
+                .collect(),
+        );
+        game.spawn_food();
+        assert_eq!(game.food(), Some(free));
+
+        game.set_obstacles(Vec::new());
+        game.snake = Snake::with_body(vec![Position::new(1, 2)], Direction::Right);
+        assert_eq!(game.update(), GameEvent::GameOver);
+    }
+
+    const DIRECTIONS: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    #[test]
+    fn test_wrapped_position_at_every_corner() {
+        let (w, h) = (5, 4);
+        let cases = [
+            // Top-left
+            (Position::new(0, 0), Direction::Up, Position::new(0, 3)),
+            (Position::new(0, 0), Direction::Down, Position::new(0, 1)),
+            (Position::new(0, 0), Direction::Left, Position::new(4, 0)),
+            (Position::new(0, 0), Direction::Right, Position::new(1, 0)),
+            // Top-right
+            (Position::new(4, 0), Direction::Up, Position::new(4, 3)),
+            (Position::new(4, 0), Direction::Down, Position::new(4, 1)),
+            (Position::new(4, 0), Direction::Left, Position::new(3, 0)),
+            (Position::new(4, 0), Direction::Right, Position::new(0, 0)),
+            // Bottom-left
+            (Position::new(0, 3), Direction::Up, Position::new(0, 2)),
+            (Position::new(0, 3), Direction::Down, Position::new(0, 0)),
+            (Position::new(0, 3), Direction::Left, Position::new(4, 3)),
+            (Position::new(0, 3), Direction::Right, Position::new(1, 3)),
+            // Bottom-right
+            (Position::new(4, 3), Direction::Up, Position::new(4, 2)),
+            (Position::new(4, 3), Direction::Down, Position::new(4, 0)),
+            (Position::new(4, 3), Direction::Left, Position::new(3, 3)),
+            (Position::new(4, 3), Direction::Right, Position::new(0, 3)),
+        ];
+
+        for (head, direction, expected) in cases {
+            assert_eq!(
+                wrapped_position(head, direction, w, h, WrapEdges::ALL),
+                Some(expected),
+                "{direction:?} from {head:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrapped_position_from_every_cell_stays_on_board_and_reverses() {
+        let (w, h) = (5, 4);
+        for x in 0..w {
+            for y in 0..h {
+                let head = Position::new(x, y);
+                for direction in DIRECTIONS {
+                    let next = wrapped_position(head, direction, w, h, WrapEdges::ALL).unwrap();
+                    assert!(next.x < w && next.y < h, "{direction:?} from {head:?}");
+                    assert_eq!(
+                        wrapped_position(next, direction.opposite(), w, h, WrapEdges::ALL),
+                        Some(head)
+                    );
+                    let (dx, dy) = direction.delta();
+                    let stepped = (i32::from(x) + dx, i32::from(y) + dy);
+                    if (0..i32::from(w)).contains(&stepped.0)
+                        && (0..i32::from(h)).contains(&stepped.1)
+                    {
+                        assert_eq!((i32::from(next.x), i32::from(next.y)), stepped);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_wrapped_position_without_wrapping_stops_at_each_edge() {
+        let (w, h) = (5, 4);
+        let none = WrapEdges::NONE;
+
+        // Every solid edge stops the step, on all four sides alike
+        let off_board = [
+            (Position::new(2, 0), Direction::Up),
+            (Position::new(0, 2), Direction::Left),
+            (Position::new(2, 3), Direction::Down),
+            (Position::new(4, 2), Direction::Right),
+            (Position::new(0, 0), Direction::Up),
+            (Position::new(4, 3), Direction::Right),
+        ];
+        for (head, direction) in off_board {
+            assert_eq!(wrapped_position(head, direction, w, h, none), None);
+        }
+
+        // Moves within the board are unaffected
+        assert_eq!(
+            wrapped_position(Position::new(4, 3), Direction::Left, w, h, none),
+            Some(Position::new(3, 3))
+        );
+    }
+
+    #[test]
+    fn test_wrapped_position_honours_each_edge_flag() {
+        let (w, h) = (5, 4);
+        let edges = [
+            (Position::new(2, 0), Direction::Up, Position::new(2, 3)),
+            (Position::new(2, 3), Direction::Down, Position::new(2, 0)),
+            (Position::new(0, 2), Direction::Left, Position::new(4, 2)),
+            (Position::new(4, 2), Direction::Right, Position::new(0, 2)),
+        ];
+
+        for (head, direction, wrapped) in edges {
+            let only_this = WrapEdges {
+                top: direction == Direction::Up,
+                bottom: direction == Direction::Down,
+                left: direction == Direction::Left,
+                right: direction == Direction::Right,
+            };
+            let all_but_this = WrapEdges {
+                top: !only_this.top,
+                bottom: !only_this.bottom,
+                left: !only_this.left,
+                right: !only_this.right,
+            };
+            assert_eq!(
+                wrapped_position(head, direction, w, h, only_this),
+                Some(wrapped)
+            );
+            assert_eq!(wrapped_position(head, direction, w, h, all_but_this), None);
+        }
+    }
+
+    #[test]
+    fn test_step_stops_at_top_and_left_edges() {
+        let origin = Position::new(0, 0);
+        assert_eq!(origin.step(Direction::Up), None);
+        assert_eq!(origin.step(Direction::Left), None);
+        assert_eq!(origin.step(Direction::Down), Some(Position::new(0, 1)));
+        assert_eq!(origin.step(Direction::Right), Some(Position::new(1, 0)));
+        assert_eq!(
+            Position::new(3, 2).step(Direction::Up),
+            Some(Position::new(3, 1))
+        );
+
+        assert_eq!(
+            origin.wrapping_step(Direction::Up, 5, 4),
+            Position::new(0, 3)
+        );
+        assert_eq!(
+            origin.wrapping_step(Direction::Left, 5, 4),
+            Position::new(4, 0)
+        );
+    }
+
+    #[test]
+    fn test_manhattan_distance() {
+        let a = Position::new(1, 7);
+        let b = Position::new(4, 3);
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(b.manhattan_distance(a), 7);
+        assert_eq!(a.manhattan_distance(a), 0);
+        assert_eq!(
+            Position::new(0, 0).manhattan_distance(Position::new(u16::MAX, u16::MAX)),
+            2 * u32::from(u16::MAX)
+        );
+    }
+
+    #[test]
+    fn test_food_avoids_projected_next_head() {
+        let spawn_on_next_head = |avoid: bool| {
+            let mut game = Game::with_seed(4, 3, 12);
+            game.set_food_avoids_next_head(avoid);
+            game.snake.body = VecDeque::from(vec![Position::new(1, 1), Position::new(0, 1)]);
+            game.snake.direction = Direction::Right;
+            let next = Position::new(2, 1);
+            (0..200)
+                .filter(|_| {
+                    game.spawn_food();
+                    game.food() == Some(next)
+                })
+                .count()
+        };
+
+        // Without the option the next cell is as likely as any other
+        assert!(spawn_on_next_head(false) > 0);
+        assert_eq!(spawn_on_next_head(true), 0);
+    }
+
+    #[test]
+    fn test_food_falls_back_to_next_head_when_nothing_else_is_free() {
+        let mut game = Game::with_seed(3, 1, 2);
+        game.set_food_avoids_next_head(true);
+        game.snake.body = VecDeque::from(vec![Position::new(1, 0), Position::new(0, 0)]);
+        game.snake.direction = Direction::Right;
+
+        game.spawn_food();
+        assert_eq!(game.food(), Some(Position::new(2, 0)));
+    }
+
+    #[test]
+    fn test_respawn_on_death_teleports_after_wall_hit() {
+        let mut game = Game::with_seed(10, 6, 9);
+        game.set_respawn_on_death(true);
+        game.score = 30;
+        game.food = Some(Position::new(0, 0));
+        game.snake.body = VecDeque::from(vec![Position::new(9, 2), Position::new(8, 2)]);
+        game.snake.direction = Direction::Right;
+
+        assert_eq!(game.update(), GameEvent::Respawned);
+        assert_eq!(game.state(), GameState::Playing);
+        assert_eq!(game.snake().len(), 1);
+        assert_eq!(game.score(), 30 - RESPAWN_PENALTY);
+        let head = game.snake().head();
+        assert!(!game.is_out_of_bounds(head) && game.food() != Some(head));
+        assert!(game.next_head().is_some());
+    }
+
+    #[test]
+    fn test_respawn_on_death_ends_game_without_free_cells() {
+        let mut game = Game::with_seed(3, 1, 9);
+        game.set_respawn_on_death(true);
+        game.food = None;
+        game.snake.body = VecDeque::from(vec![
+            Position::new(2, 0),
+            Position::new(1, 0),
+            Position::new(0, 0),
+        ]);
+        // Hitting the top wall leaves the snake filling the whole board
+        game.snake.direction = Direction::Up;
+
+        assert_eq!(game.update(), GameEvent::GameOver);
+    }
+
+    #[test]
+    fn test_advance_returns_dropped_tail() {
+        let body = vec![
+            Position::new(3, 1),
+            Position::new(2, 1),
+            Position::new(1, 1),
+        ];
+        let mut snake = Snake::with_body(body, Direction::Right);
+
+        assert_eq!(snake.advance(Position::new(4, 1)), Position::new(1, 1));
+        assert_eq!(snake.head(), Position::new(4, 1));
+        assert_eq!(snake.len(), 3);
+        assert_eq!(snake.body().back(), Some(&Position::new(2, 1)));
+    }
+
+    #[test]
+    fn test_grow_by_adds_exactly_n_segments() {
+        let mut snake = Snake::with_body(
+            vec![Position::new(3, 1), Position::new(2, 1)],
+            Direction::Right,
+        );
+
+        snake.grow_by(3);
+        assert_eq!(snake.len(), 5);
+        snake.grow_by(0);
+        assert_eq!(snake.len(), 5);
+
+        // The stacked segments trail out as the snake moves
+        snake.move_forward();
+        assert_eq!(snake.len(), 5);
+        assert_eq!(snake.body()[2], Position::new(2, 1));
+    }
+
+    #[test]
+    fn test_quick_meals_score_a_combo() {
+        let mut game = Game::with_seed(30, 10, 5);
+        game.set_combo(3, 2);
+        game.snake.body = VecDeque::from(vec![Position::new(5, 2)]);
+        game.snake.direction = Direction::Right;
+        let eat_at = |game: &mut Game, x: u16| {
+            game.food = Some(Position::new(x, 2));
+            game.food_kind = FoodKind::Normal;
+            game.big_food = None;
+            game.power_up = None;
+            loop {
+                let event = game.update();
+                if game.snake().head().x == x {
+                    return event;
+                }
+            }
+        };
+
+        // Nothing to chain from on the first meal
+        assert_eq!(
+            eat_at(&mut game, 6),
+            GameEvent::FoodEaten { value: FOOD_POINTS }
+        );
+        assert_eq!(eat_at(&mut game, 8), GameEvent::Combo(2));
+        assert_eq!(game.score(), 3 * FOOD_POINTS);
+        assert_eq!(game.combo(), 1);
+
+        // Too slow: the combo is over
+        assert_eq!(
+            eat_at(&mut game, 14),
+            GameEvent::FoodEaten { value: FOOD_POINTS }
+        );
+        assert_eq!(game.score(), 4 * FOOD_POINTS);
+        assert_eq!(game.combo(), 0);
+
+        game.reset();
+        assert_eq!(game.combo(), 0);
+    }
+
+    #[test]
+    fn test_hunger_decays_score_until_food_is_eaten() {
+        let mut game = Game::with_seed(30, 10, 5);
+        game.set_hunger(Some(2));
+        game.score = 1;
+        game.food = Some(Position::new(0, 9));
+        game.snake.body = VecDeque::from(vec![Position::new(5, 2)]);
+        game.snake.direction = Direction::Right;
+
+        game.update();
+        assert_eq!(game.score(), 1);
+        game.update();
+        assert_eq!(game.score(), 0);
+
+        // The score saturates at zero instead of underflowing
+        game.update();
+        game.update();
+        assert_eq!(game.score(), 0);
+        assert_eq!(game.ticks_since_food(), 4);
+
+        game.food = Some(Position::new(10, 2));
+        game.food_kind = FoodKind::Normal;
+        assert_eq!(game.update(), GameEvent::FoodEaten { value: FOOD_POINTS });
+        assert_eq!(game.score(), FOOD_POINTS);
+        assert_eq!(game.ticks_since_food(), 0);
+    }
+
+    #[test]
+    fn test_run_steps_is_deterministic_and_stops_at_game_over() {
+        let directions = [
+            Direction::Down,
+            Direction::Down,
+            Direction::Left,
+            Direction::Left,
+            Direction::Up,
+        ];
+
+        let mut first = Game::with_seed(20, 12, 9);
+        let mut second = Game::with_seed(20, 12, 9);
+        let events = first.run_steps(50, &directions);
+
+        assert_eq!(events, second.run_steps(50, &directions));
+        assert_eq!(first.snake().head(), second.snake().head());
+        assert_eq!(first.score(), second.score());
+
+        // Heading up from the middle of a solid-walled board ends the game
+        assert!(events.len() < 50);
+        assert_eq!(events.last(), Some(&GameEvent::GameOver));
+        assert_eq!(first.state(), GameState::GameOver);
+    }
+
+    #[test]
+    fn test_quick_turns_within_a_tick_cannot_reverse() {
+        let mut game = Game::with_seed(20, 20, 2);
+        game.food = Some(Position::new(0, 0));
+        game.snake.body = VecDeque::from(vec![
+            Position::new(10, 10),
+            Position::new(9, 10),
+            Position::new(8, 10),
+        ]);
+        game.snake.direction = Direction::Right;
+        game.committed_direction = Direction::Right;
+
+        // Up then Left before the next tick would turn straight back into the body
+        game.set_direction(Direction::Up);
+        game.set_direction(Direction::Left);
+        game.update();
+
+        assert_eq!(game.state(), GameState::Playing);
+        assert!(!game.snake().check_self_collision());
+        assert_eq!(game.snake().head(), Position::new(10, 9));
+    }
+
+    #[test]
+    fn test_seeded_game_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Game>();
+
+        // Simulations can run on worker threads
+        let mut game = Game::with_seed(20, 12, 6);
+        let events = std::thread::spawn(move || game.run_steps(5, &[]))
+            .join()
+            .unwrap();
+        assert_eq!(events.len(), 5);
+    }
+
+    #[test]
+    fn test_text_grid_shows_head_at_its_cell() {
+        let mut game = Game::with_seed(6, 4, 11);
+        game.food = Some(Position::new(0, 0));
+        game.snake.body = VecDeque::from(vec![Position::new(3, 2), Position::new(2, 2)]);
+
+        let text = game.to_text_grid();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "Score: 0 Seed: 11");
+        assert_eq!(lines.len(), 1 + 4);
+        assert_eq!(lines[1], "◆·····");
+        assert_eq!(lines[3].chars().nth(3), Some('●'));
+        assert_eq!(lines[3].chars().nth(2), Some('○'));
+
+        // Soft obstacles look lighter than solid ones, like in the TUI
+        game.set_obstacles(vec![Position::new(5, 3)]);
+        assert_eq!(game.to_text_grid().lines().nth(4), Some("·····▓"));
+        game.set_solid_obstacles(false);
+        assert_eq!(game.to_text_grid().lines().nth(4), Some("·····▒"));
+    }
+
+    #[test]
+    fn test_configured_initial_direction() {
+        let config = GameConfig::new(20, 10).with_initial_direction(Direction::Up);
+        let mut game = Game::from_config(&config);
+
+        assert_eq!(game.snake().direction(), Direction::Up);
+        let body: Vec<_> = game.snake().body().iter().copied().collect();
+        assert!(body.windows(2).all(|pair| pair[1].y == pair[0].y + 1));
+        assert!(body.iter().all(|&pos| !game.is_out_of_bounds(pos)));
+
+        // Reversing into the body is still refused
+        game.set_direction(Direction::Down);
+        assert_eq!(game.snake().direction(), Direction::Up);
+
+        game.reset();
+        assert_eq!(game.snake().direction(), Direction::Up);
+    }
+
+    #[test]
+    fn test_seeded_food_is_reproducible_with_laid_out_snake() {
+        let food_with_snake = |length: u16| {
+            let mut game = Game::with_rng(20, 20, Some(9), StdRng::seed_from_u64(9));
+            let body = (0..length).map(|i| Position::new(10 - i, 0)).collect();
+            game.snake = Snake::with_body(body, Direction::Right);
+            game.spawn_food();
+            game.food()
+        };
+
+        // Laying out the body draws nothing from the RNG, so the same seed and
+        // body always place the same food
+        for length in [2, 5] {
+            assert_eq!(food_with_snake(length), food_with_snake(length));
+        }
+    }
+
+    #[test]
+    fn test_start_paused_waits_for_resume() {
+        let config = GameConfig::new(20, 10).with_start_paused(true);
+        let mut game = Game::from_config(&config);
+        let head = game.snake().head();
+        assert_eq!(game.state(), GameState::Paused);
+
+        game.update();
+        assert_eq!(game.snake().head(), head);
+
+        game.toggle_pause();
+        game.update();
+        assert_eq!(game.snake().head(), Position::new(head.x + 1, head.y));
+    }
+
+    #[test]
+    fn test_food_respawns_after_delay() {
+        let mut game = Game::with_seed(20, 20, 3);
+        game.set_respawn_delay(3);
+        let head = game.snake().head();
+        game.food = Some(Position::new(head.x + 1, head.y));
+
+        assert!(matches!(game.update(), GameEvent::FoodEaten { .. }));
+        for _ in 0..2 {
+            assert_eq!(game.food(), None);
+            game.update();
+        }
+        assert_eq!(game.food(), None);
+
+        game.update();
+        assert!(game.food().is_some());
+    }
+
+    #[test]
+    fn test_golden_food_scores_more() {
+        let mut game = Game::with_seed(20, 20, 13);
+        game.set_golden_food_chance(1.0);
+        game.reset();
+        assert_eq!(game.food_kind(), FoodKind::Golden);
+
+        let food = game.food().unwrap();
+        let start = if food.x > 0 {
+            Position::new(food.x - 1, food.y)
+        } else {
+            Position::new(food.x + 1, food.y)
+        };
+        game.snake.body = VecDeque::from(vec![start]);
+        game.snake.direction = if food.x > 0 {
+            Direction::Right
+        } else {
+            Direction::Left
+        };
+        game.committed_direction = game.snake.direction;
+
+        assert_eq!(
+            game.update(),
+            GameEvent::FoodEaten {
+                value: GOLDEN_FOOD_POINTS
+            }
+        );
+        assert_eq!(game.score(), GOLDEN_FOOD_POINTS);
+        assert!(GOLDEN_FOOD_POINTS > FoodKind::Normal.points());
+    }
+
+    #[test]
+    fn test_open_board_is_not_trapped() {
+        let mut game = Game::with_seed(10, 10, 1);
+        game.food = Some(Position::new(8, 8));
+        game.snake.body = VecDeque::from(vec![
+            Position::new(2, 2),
+            Position::new(1, 2),
+            Position::new(0, 2),
+        ]);
+
+        assert!(!game.is_trapped());
+    }
+
+    #[test]
+    fn test_food_walled_off_by_body_is_trapped() {
+        let mut game = Game::with_seed(10, 10, 1);
+        game.food = Some(Position::new(0, 0));
+        // The body seals off the top-left corner from the head
+        game.snake.body = VecDeque::from(vec![
+            Position::new(3, 0),
+            Position::new(2, 0),
+            Position::new(2, 1),
+            Position::new(2, 2),
+            Position::new(1, 2),
+            Position::new(0, 2),
+        ]);
+
+        assert!(game.is_trapped());
+
+        // With wrapping the corner is reachable the other way around
+        game.set_wall_wrapping(true);
+        assert!(!game.is_trapped());
+    }
+
+    #[test]
+    fn test_food_walled_off_by_spikes_is_trapped() {
+        let mut game = Game::with_seed(10, 10, 1);
+        game.set_wall_wrapping(false);
+        game.food = Some(Position::new(0, 0));
+        game.set_spikes(vec![
+            Position::new(2, 0),
+            Position::new(2, 1),
+            Position::new(2, 2),
+            Position::new(1, 2),
+            Position::new(0, 2),
+        ]);
+        assert!(game.is_trapped());
+
+        // A gap in the spikes lets the snake through
+        game.set_spikes(vec![
+            Position::new(2, 0),
+            Position::new(2, 1),
+            Position::new(2, 2),
+            Position::new(0, 2),
+        ]);
+        assert!(!game.is_trapped());
+    }
+
+    #[test]
+    fn test_reset_can_keep_score() {
+        let mut game = Game::with_seed(20, 10, 2);
+        game.set_keep_score_on_restart(true);
+        game.score = 70;
+        game.state = GameState::GameOver;
+        game.snake.body = VecDeque::from(vec![Position::new(0, 0)]);
+
+        game.reset();
+
+        assert_eq!(game.score(), 70);
+        assert_eq!(game.state(), GameState::Playing);
+        assert_eq!(game.snake().len(), usize::from(INITIAL_SNAKE_LENGTH));
+
+        game.set_keep_score_on_restart(false);
+        game.reset();
+        assert_eq!(game.score(), 0);
+    }
+
+    #[test]
+    fn test_last_eaten_set_only_on_eating_tick() {
+        let mut game = Game::with_seed(20, 20, 3);
+        game.snake.body = VecDeque::from(vec![Position::new(5, 5), Position::new(4, 5)]);
+        game.snake.direction = Direction::Right;
+        game.food = Some(Position::new(6, 5));
+        assert_eq!(game.last_eaten(), None);
+
+        assert!(matches!(game.update(), GameEvent::FoodEaten { .. }));
+        assert_eq!(game.last_eaten(), Some(Position::new(6, 5)));
+
+        // Move away from the freshly spawned food so the next tick is a plain move
+        game.food = Some(Position::new(0, 0));
+        assert_eq!(game.update(), GameEvent::Moved);
+        assert_eq!(game.last_eaten(), None);
     }
 }
@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const HIGH_SCORE_FILE_NAME: &str = ".snake_rs_high_scores.json";
+
+/// Best results recorded for one board size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonalBests {
+    pub score: u32,
+    #[serde(default)]
+    pub length: usize,
+    #[serde(default)]
+    pub survival_secs: u64,
+}
+
+impl PersonalBests {
+    /// Keeps the better value of each record, returning whether any improved.
+    pub fn improve(&mut self, other: &PersonalBests) -> bool {
+        let before = *self;
+        self.score = self.score.max(other.score);
+        self.length = self.length.max(other.length);
+        self.survival_secs = self.survival_secs.max(other.survival_secs);
+        *self != before
+    }
+}
+
+/// Files written before lengths and survival times were tracked store only
+/// the score for each board size.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredRecord {
+    Score(u32),
+    Bests(PersonalBests),
+}
+
+/// Default location of the high score file: the user's home directory,
+/// falling back to the current directory.
+pub fn default_high_score_path() -> PathBuf {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(HIGH_SCORE_FILE_NAME)
+}
+
+/// Loads personal bests keyed by `(board_width, board_height)`.
+///
+/// A missing or malformed file yields an empty table rather than an error,
+/// so a corrupt score file never prevents the game from starting.
+pub fn load_personal_bests(path: &Path) -> HashMap<(u16, u16), PersonalBests> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    let Ok(raw) = serde_json::from_str::<HashMap<String, StoredRecord>>(&contents) else {
+        return HashMap::new();
+    };
+
+    raw.into_iter()
+        .filter_map(|(key, record)| {
+            let bests = match record {
+                StoredRecord::Score(score) => PersonalBests {
+                    score,
+                    ..Default::default()
+                },
+                StoredRecord::Bests(bests) => bests,
+            };
+            parse_board_key(&key).map(|size| (size, bests))
+        })
+        .collect()
+}
+
+pub fn save_personal_bests(
+    path: &Path,
+    bests: &HashMap<(u16, u16), PersonalBests>,
+) -> io::Result<()> {
+    // JSON object keys must be strings, so sizes are stored as "WIDTHxHEIGHT"
+    let raw: HashMap<String, PersonalBests> = bests
+        .iter()
+        .map(|(&(width, height), &record)| (format!("{}x{}", width, height), record))
+        .collect();
+    let contents = serde_json::to_string_pretty(&raw).map_err(io::Error::other)?;
+    fs::write(path, contents)
+}
+
+/// Loads high scores keyed by `(board_width, board_height)`.
+pub fn load_high_scores(path: &Path) -> HashMap<(u16, u16), u32> {
+    load_personal_bests(path)
+        .into_iter()
+        .map(|(size, bests)| (size, bests.score))
+        .collect()
+}
+
+/// Saves high scores, keeping any other personal bests already in the file.
+pub fn save_high_scores(path: &Path, scores: &HashMap<(u16, u16), u32>) -> io::Result<()> {
+    let mut bests = load_personal_bests(path);
+    for (&size, &score) in scores {
+        bests.entry(size).or_default().score = score;
+    }
+    save_personal_bests(path, &bests)
+}
+
+fn parse_board_key(key: &str) -> Option<(u16, u16)> {
+    let (width, height) = key.split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("snake_rs_{}_{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_high_scores_round_trip() {
+        let path = temp_path("high_scores_test");
+
+        let mut scores = HashMap::new();
+        scores.insert((30, 20), 120);
+        scores.insert((10, 10), 40);
+        save_high_scores(&path, &scores).unwrap();
+
+        let loaded = load_high_scores(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.get(&(30, 20)), Some(&120));
+        assert_eq!(loaded.get(&(10, 10)), Some(&40));
+        assert_eq!(loaded.get(&(20, 30)), None);
+    }
+
+    #[test]
+    fn test_loads_score_only_records() {
+        let path = temp_path("legacy_scores_test");
+        fs::write(&path, r#"{ "30x20": 150 }"#).unwrap();
+
+        let loaded = load_personal_bests(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            loaded.get(&(30, 20)),
+            Some(&PersonalBests {
+                score: 150,
+                length: 0,
+                survival_secs: 0,
+            })
+        );
+    }
+}
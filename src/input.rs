@@ -1,11 +1,21 @@
+use crate::clock::MockClock;
 use crate::game::Direction;
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, collections::VecDeque, io, time::Duration};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InputAction {
     Move(Direction),
+    Boost,
     Pause,
     Restart,
+    Snapshot,
+    ToggleWalls,
+    CycleTheme,
     Quit,
     None,
 }
@@ -14,9 +24,171 @@ pub trait InputHandler {
     type Error;
 
     fn handle_input(&self, event: Event) -> Result<InputAction, Self::Error>;
+
+    /// Tells the handler where the snake head is on screen (column, row), for
+    /// handlers that interpret pointer input relative to it.
+    fn set_head_position(&mut self, _position: Option<(u16, u16)>) {}
+
+    /// Waits up to `timeout` for the next event. The default reads from the
+    /// terminal.
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        if event::poll(timeout)? {
+            Ok(Some(event::read()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Direction from `from` towards `to` in screen coordinates (column, row),
+/// picking the axis with the larger distance. Columns count half since board
+/// cells are two columns wide.
+pub fn direction_towards(from: (u16, u16), to: (u16, u16)) -> Option<Direction> {
+    let dx = (i32::from(to.0) - i32::from(from.0)) / 2;
+    let dy = i32::from(to.1) - i32::from(from.1);
+
+    if dx == 0 && dy == 0 {
+        None
+    } else if dx.abs() >= dy.abs() {
+        Some(if dx > 0 {
+            Direction::Right
+        } else {
+            Direction::Left
+        })
+    } else {
+        Some(if dy > 0 {
+            Direction::Down
+        } else {
+            Direction::Up
+        })
+    }
+}
+
+/// Which key triggers which action.
+///
+/// A key maps to at most one action, while an action can have any number of
+/// keys. The default binds arrows, WASD and vi keys for movement.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyMap {
+    bindings: Vec<(KeyCode, InputAction)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        use Direction::{Down, Left, Right, Up};
+        use InputAction::{Boost, CycleTheme, Move, Pause, Quit, Restart, Snapshot, ToggleWalls};
+
+        let bindings = vec![
+            (KeyCode::Up, Move(Up)),
+            (KeyCode::Char('w'), Move(Up)),
+            (KeyCode::Char('k'), Move(Up)),
+            (KeyCode::Down, Move(Down)),
+            (KeyCode::Char('s'), Move(Down)),
+            (KeyCode::Char('j'), Move(Down)),
+            (KeyCode::Left, Move(Left)),
+            (KeyCode::Char('a'), Move(Left)),
+            (KeyCode::Char('h'), Move(Left)),
+            (KeyCode::Right, Move(Right)),
+            (KeyCode::Char('d'), Move(Right)),
+            (KeyCode::Char('l'), Move(Right)),
+            (KeyCode::Char(' '), Pause),
+            (KeyCode::Char('b'), Boost),
+            (KeyCode::Char('B'), Boost),
+            (KeyCode::Char('r'), Restart),
+            (KeyCode::Char('R'), Restart),
+            (KeyCode::Char('p'), Snapshot),
+            (KeyCode::Char('P'), Snapshot),
+            (KeyCode::Char('t'), ToggleWalls),
+            (KeyCode::Char('T'), ToggleWalls),
+            (KeyCode::Char('c'), CycleTheme),
+            (KeyCode::Char('C'), CycleTheme),
+            (KeyCode::Char('q'), Quit),
+            (KeyCode::Char('Q'), Quit),
+            (KeyCode::Esc, Quit),
+        ];
+        Self { bindings }
+    }
+}
+
+impl KeyMap {
+    /// Binds `action` to exactly `keys`, replacing its previous keys. Keys
+    /// that were bound to another action are moved over to this one.
+    pub fn bind(mut self, action: InputAction, keys: &[KeyCode]) -> Self {
+        self.bindings
+            .retain(|(key, bound)| *bound != action && !keys.contains(key));
+        self.bindings.extend(keys.iter().map(|&key| (key, action)));
+        self
+    }
+
+    pub fn action(&self, code: KeyCode) -> InputAction {
+        self.bindings
+            .iter()
+            .find(|(key, _)| *key == code)
+            .map_or(InputAction::None, |&(_, action)| action)
+    }
+
+    pub fn keys_for(&self, action: InputAction) -> Vec<KeyCode> {
+        self.bindings
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|&(key, _)| key)
+            .collect()
+    }
+
+    /// Short label for the keys of `action`, showing at most two of them,
+    /// e.g. "Q/Esc". Empty if the action is unbound.
+    pub fn hint(&self, action: InputAction) -> String {
+        let mut names: Vec<String> = Vec::new();
+        for key in self.keys_for(action) {
+            let name = key_name(key);
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names.truncate(2);
+        names.join("/")
+    }
+
+    /// Label for the movement keys: the first two complete sets of
+    /// up/left/down/right keys, e.g. "↑←↓→/WASD".
+    pub fn move_hint(&self) -> String {
+        let keys = [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+        ]
+        .map(|direction| self.keys_for(InputAction::Move(direction)));
+
+        (0..2)
+            .filter_map(|i| {
+                keys.iter()
+                    .map(|keys| keys.get(i).map(|&key| key_name(key)))
+                    .collect::<Option<String>>()
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
 }
 
-pub struct CrosstermInputHandler;
+/// Display name of a key for the controls panel.
+pub fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Up => String::from("↑"),
+        KeyCode::Down => String::from("↓"),
+        KeyCode::Left => String::from("←"),
+        KeyCode::Right => String::from("→"),
+        KeyCode::Char(' ') => String::from("Space"),
+        KeyCode::Char(c) => c.to_uppercase().collect(),
+        other => other.to_string(),
+    }
+}
+
+pub struct CrosstermInputHandler {
+    mouse_enabled: bool,
+    head_position: Option<(u16, u16)>,
+    key_map: KeyMap,
+}
 
 impl Default for CrosstermInputHandler {
     fn default() -> Self {
@@ -26,7 +198,22 @@ impl Default for CrosstermInputHandler {
 
 impl CrosstermInputHandler {
     pub fn new() -> Self {
-        Self
+        Self {
+            mouse_enabled: false,
+            head_position: None,
+            key_map: KeyMap::default(),
+        }
+    }
+
+    pub fn with_key_map(mut self, key_map: KeyMap) -> Self {
+        self.key_map = key_map;
+        self
+    }
+
+    /// Lets left clicks steer the snake towards the clicked cell.
+    pub fn with_mouse(mut self, enabled: bool) -> Self {
+        self.mouse_enabled = enabled;
+        self
     }
 }
 
@@ -34,28 +221,294 @@ impl InputHandler for CrosstermInputHandler {
     type Error = std::io::Error;
 
     fn handle_input(&self, event: Event) -> Result<InputAction, Self::Error> {
-        if let Event::Key(KeyEvent { code, .. }) = event {
-            let action = match code {
-                KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('k') => {
-                    InputAction::Move(Direction::Up)
-                }
-                KeyCode::Down | KeyCode::Char('s') | KeyCode::Char('j') => {
-                    InputAction::Move(Direction::Down)
-                }
-                KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('h') => {
-                    InputAction::Move(Direction::Left)
+        if let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind,
+            ..
+        }) = event
+        {
+            // Some terminals report releases too, which would act twice
+            if kind == KeyEventKind::Release {
+                return Ok(InputAction::None);
+            }
+            // Raw mode turns Ctrl-C into a key press instead of SIGINT
+            if modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('c') {
+                return Ok(InputAction::Quit);
+            }
+            // Leave modified keys to the terminal's own shortcuts. Shift only
+            // picks the character, e.g. an uppercase letter, so it's fine
+            if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER)
+            {
+                return Ok(InputAction::None);
+            }
+            match (self.key_map.action(code), code) {
+                // With Caps Lock on, letters still act as their lowercase keys
+                (InputAction::None, KeyCode::Char(c)) if c.is_uppercase() => {
+                    Ok(self.key_map.action(KeyCode::Char(c.to_ascii_lowercase())))
                 }
-                KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('l') => {
-                    InputAction::Move(Direction::Right)
-                }
-                KeyCode::Char(' ') => InputAction::Pause,
-                KeyCode::Char('r') | KeyCode::Char('R') => InputAction::Restart,
-                KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => InputAction::Quit,
-                _ => InputAction::None,
-            };
-            Ok(action)
+                (action, _) => Ok(action),
+            }
+        } else if let Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            ..
+        }) = event
+        {
+            let direction = self
+                .head_position
+                .filter(|_| self.mouse_enabled)
+                .and_then(|head| direction_towards(head, (column, row)));
+            Ok(direction.map_or(InputAction::None, InputAction::Move))
         } else {
             Ok(InputAction::None)
         }
     }
+
+    fn set_head_position(&mut self, position: Option<(u16, u16)>) {
+        self.head_position = position;
+    }
+}
+
+/// Input handler that plays back a fixed list of actions, one per event,
+/// without touching the terminal. Meant for driving the app in tests.
+pub struct ScriptedInputHandler {
+    actions: RefCell<VecDeque<InputAction>>,
+    clock: Option<MockClock>,
+}
+
+impl ScriptedInputHandler {
+    pub fn new(actions: impl IntoIterator<Item = InputAction>) -> Self {
+        Self {
+            actions: RefCell::new(actions.into_iter().collect()),
+            clock: None,
+        }
+    }
+
+    /// Waits on `clock` instead of sleeping once the script runs out, so an
+    /// app driven by the same mock clock never waits in real time.
+    pub fn with_clock(mut self, clock: MockClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Number of actions that haven't been played back yet.
+    pub fn remaining(&self) -> usize {
+        self.actions.borrow().len()
+    }
+}
+
+impl InputHandler for ScriptedInputHandler {
+    type Error = io::Error;
+
+    fn handle_input(&self, _event: Event) -> Result<InputAction, Self::Error> {
+        Ok(self
+            .actions
+            .borrow_mut()
+            .pop_front()
+            .unwrap_or(InputAction::None))
+    }
+
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        if self.remaining() > 0 {
+            // The event itself is ignored, it only prompts the next action
+            Ok(Some(Event::FocusGained))
+        } else {
+            // Out of script, so behave like a player who stopped typing
+            match &self.clock {
+                Some(clock) => clock.advance(timeout),
+                None => std::thread::sleep(timeout),
+            }
+            Ok(None)
+        }
+    }
+}
+
+/// Queue of direction changes waiting for upcoming ticks.
+///
+/// Only one direction change is applied per tick; further presses within the
+/// same tick are queued here up to `capacity`. Once full, the oldest queued
+/// press makes way for the newest.
+pub struct DirectionBuffer {
+    queue: VecDeque<Direction>,
+    capacity: usize,
+}
+
+impl DirectionBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            queue: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Queues a direction, dropping the oldest one if the buffer is full.
+    /// Returns `false` only if the buffer has no capacity at all.
+    pub fn push(&mut self, direction: Direction) -> bool {
+        if self.capacity == 0 {
+            return false;
+        }
+        if self.queue.len() >= self.capacity {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(direction);
+        true
+    }
+
+    pub fn pop(&mut self) -> Option<Direction> {
+        self.queue.pop_front()
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn test_mouse_click_maps_to_direction_from_head() {
+        let mut handler = CrosstermInputHandler::new().with_mouse(true);
+        handler.set_head_position(Some((20, 10)));
+
+        let action = |event| handler.handle_input(event).unwrap();
+        assert_eq!(action(click(30, 11)), InputAction::Move(Direction::Right));
+        assert_eq!(action(click(10, 9)), InputAction::Move(Direction::Left));
+        assert_eq!(action(click(21, 4)), InputAction::Move(Direction::Up));
+        assert_eq!(action(click(18, 15)), InputAction::Move(Direction::Down));
+        assert_eq!(action(click(20, 10)), InputAction::None);
+    }
+
+    #[test]
+    fn test_mouse_ignored_unless_enabled() {
+        let mut handler = CrosstermInputHandler::new();
+        handler.set_head_position(Some((20, 10)));
+
+        assert_eq!(
+            handler.handle_input(click(30, 10)).unwrap(),
+            InputAction::None
+        );
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn test_ctrl_c_quits() {
+        let handler = CrosstermInputHandler::new();
+        let action = |event| handler.handle_input(event).unwrap();
+
+        assert_eq!(
+            action(key(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            InputAction::Quit
+        );
+        assert_eq!(
+            action(key(KeyCode::Char('c'), KeyModifiers::NONE)),
+            InputAction::CycleTheme
+        );
+    }
+
+    #[test]
+    fn test_modified_keys_are_ignored() {
+        let handler = CrosstermInputHandler::new();
+        let action = |event| handler.handle_input(event).unwrap();
+
+        assert_eq!(
+            action(key(KeyCode::Char('r'), KeyModifiers::NONE)),
+            InputAction::Restart
+        );
+        assert_eq!(
+            action(key(KeyCode::Char('r'), KeyModifiers::CONTROL)),
+            InputAction::None
+        );
+        assert_eq!(
+            action(key(KeyCode::Char('r'), KeyModifiers::ALT)),
+            InputAction::None
+        );
+        assert_eq!(
+            action(key(KeyCode::Char('Q'), KeyModifiers::SHIFT)),
+            InputAction::Quit
+        );
+        // Caps Lock letters fall back to their lowercase binding
+        assert_eq!(
+            action(key(KeyCode::Char('W'), KeyModifiers::SHIFT)),
+            InputAction::Move(Direction::Up)
+        );
+        assert_eq!(
+            action(key(KeyCode::Up, KeyModifiers::NONE)),
+            InputAction::Move(Direction::Up)
+        );
+    }
+
+    #[test]
+    fn test_key_release_is_ignored() {
+        let handler = CrosstermInputHandler::new();
+        let release =
+            KeyEvent::new_with_kind(KeyCode::Left, KeyModifiers::NONE, KeyEventKind::Release);
+        let repeat =
+            KeyEvent::new_with_kind(KeyCode::Left, KeyModifiers::NONE, KeyEventKind::Repeat);
+
+        assert_eq!(
+            handler.handle_input(Event::Key(release)).unwrap(),
+            InputAction::None
+        );
+        assert_eq!(
+            handler.handle_input(Event::Key(repeat)).unwrap(),
+            InputAction::Move(Direction::Left)
+        );
+    }
+
+    #[test]
+    fn test_rebinding_moves_key_between_actions() {
+        let key_map = KeyMap::default().bind(InputAction::Pause, &[KeyCode::Char('p')]);
+
+        assert_eq!(key_map.action(KeyCode::Char('p')), InputAction::Pause);
+        assert_eq!(key_map.action(KeyCode::Char(' ')), InputAction::None);
+        assert_eq!(key_map.hint(InputAction::Pause), "P");
+        assert_eq!(key_map.hint(InputAction::Quit), "Q/Esc");
+        assert_eq!(key_map.move_hint(), "↑←↓→/WASD");
+    }
+
+    #[test]
+    fn test_direction_buffer_drops_oldest_on_overflow() {
+        let mut buffer = DirectionBuffer::new(2);
+
+        assert!(buffer.push(Direction::Up));
+        assert!(buffer.push(Direction::Left));
+        assert!(buffer.push(Direction::Down));
+        assert_eq!(buffer.len(), 2);
+
+        assert_eq!(buffer.pop(), Some(Direction::Left));
+        assert_eq!(buffer.pop(), Some(Direction::Down));
+        assert_eq!(buffer.pop(), None);
+    }
+
+    #[test]
+    fn test_zero_capacity_buffer_queues_nothing() {
+        let mut buffer = DirectionBuffer::new(0);
+
+        assert!(!buffer.push(Direction::Up));
+        assert!(buffer.is_empty());
+    }
 }
@@ -1,15 +1,39 @@
 // lib.rs - Library interface for snake_rs
+pub mod achievements;
+pub mod ai;
 pub mod app;
+pub mod clock;
 pub mod config;
+pub mod daily;
+pub mod death_replay;
 pub mod game;
+pub mod high_scores;
 pub mod input;
+pub mod messages;
+pub mod power_up;
+pub mod replay;
+pub mod scenario;
 pub mod renderer;
 pub mod sound;
+pub mod stamina;
+pub mod summary;
 
 // Re-export commonly used items
+pub use achievements::{Achievement, Achievements, Milestone};
+pub use ai::autopilot_direction;
 pub use app::App;
-pub use config::GameConfig;
-pub use game::{Game, Direction, GameState, GameEvent, Position};
-pub use input::{InputAction, InputHandler, CrosstermInputHandler};
-pub use renderer::{Renderer, TuiRenderer};
+pub use clock::{Clock, MockClock, SystemClock};
+pub use config::{Anchor, ConfigError, GameConfig, ScoreFormat, Theme};
+pub use daily::Date;
+pub use death_replay::DeathReplay;
+pub use game::{Game, Direction, GameState, GameEvent, GameSnapshot, Position, BoardShape, FoodKind, WrapEdges, wrapped_position};
+pub use high_scores::{PersonalBests, load_high_scores, load_personal_bests, save_high_scores, save_personal_bests};
+pub use input::{InputAction, InputHandler, CrosstermInputHandler, DirectionBuffer, KeyMap, ScriptedInputHandler};
+pub use messages::Messages;
+pub use power_up::{PowerUpKind, SpeedEffect};
+pub use replay::replay_hash;
+pub use scenario::{Scenario, ScenarioError};
+pub use renderer::{BoardLayout, FrameContext, Renderer, TuiRenderer, fit_board_to_terminal};
 pub use sound::{SoundSystem, ConsoleSoundSystem, NoSoundSystem};
+pub use stamina::Stamina;
+pub use summary::{GameSummary, Outcome};
@@ -1,13 +1,133 @@
-use clap::Parser;
-use ratatui::style::Color;
-use snake_rs::{app, config, input, sound};
-use std::io;
+use clap::{Parser, ValueEnum};
+use ratatui::{style::Color, widgets::BorderType};
+use snake_rs::{app, config, daily, game, high_scores, input, messages, renderer, scenario, sound};
+use std::{io, path::PathBuf, time::Duration};
 
 use app::{App, restore_terminal, setup_terminal};
-use config::GameConfig;
+use config::{Anchor, GameConfig, ScoreFormat};
+use daily::Date;
+use game::{BoardShape, Direction, WrapEdges};
 use input::CrosstermInputHandler;
+use messages::Messages;
+use renderer::TuiRenderer;
+use scenario::Scenario;
 use sound::ConsoleSoundSystem;
 
+/// Border styles selectable from the command line
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum BorderStyle {
+    Plain,
+    Rounded,
+    Double,
+    Thick,
+}
+
+impl From<BorderStyle> for BorderType {
+    fn from(style: BorderStyle) -> Self {
+        match style {
+            BorderStyle::Plain => BorderType::Plain,
+            BorderStyle::Rounded => BorderType::Rounded,
+            BorderStyle::Double => BorderType::Double,
+            BorderStyle::Thick => BorderType::Thick,
+        }
+    }
+}
+
+/// Board shapes selectable from the command line
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Shape {
+    Rectangle,
+    Circle,
+    Diamond,
+}
+
+impl From<Shape> for BoardShape {
+    fn from(shape: Shape) -> Self {
+        match shape {
+            Shape::Rectangle => BoardShape::Rectangle,
+            Shape::Circle => BoardShape::Circle,
+            Shape::Diamond => BoardShape::Diamond,
+        }
+    }
+}
+
+/// Board placements selectable from the command line
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Placement {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl From<Placement> for Anchor {
+    fn from(placement: Placement) -> Self {
+        match placement {
+            Placement::TopLeft => Anchor::TopLeft,
+            Placement::Top => Anchor::Top,
+            Placement::TopRight => Anchor::TopRight,
+            Placement::Left => Anchor::Left,
+            Placement::Center => Anchor::Center,
+            Placement::Right => Anchor::Right,
+            Placement::BottomLeft => Anchor::BottomLeft,
+            Placement::Bottom => Anchor::Bottom,
+            Placement::BottomRight => Anchor::BottomRight,
+        }
+    }
+}
+
+/// Score formats selectable from the command line
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ScoreStyle {
+    Plain,
+    Thousands,
+    Padded,
+}
+
+impl From<ScoreStyle> for ScoreFormat {
+    fn from(style: ScoreStyle) -> Self {
+        match style {
+            ScoreStyle::Plain => ScoreFormat::Plain,
+            ScoreStyle::Thousands => ScoreFormat::Thousands,
+            ScoreStyle::Padded => ScoreFormat::ZeroPadded,
+        }
+    }
+}
+
+/// Starting directions selectable from the command line
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Heading {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl From<Heading> for Direction {
+    fn from(heading: Heading) -> Self {
+        match heading {
+            Heading::Up => Direction::Up,
+            Heading::Down => Direction::Down,
+            Heading::Left => Direction::Left,
+            Heading::Right => Direction::Right,
+        }
+    }
+}
+
+/// Board edges selectable for wrapping from the command line
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
 /// A terminal-based Snake game written in Rust
 #[derive(Parser, Debug)]
 #[command(name = "snake_rs")]
@@ -22,45 +142,446 @@ struct Args {
     #[arg(long = "solid-walls", short = 's')]
     solid_walls: bool,
 
+    /// Start with solid walls that wrap once the score reaches this value
+    #[arg(long = "wrap-after", value_name = "SCORE")]
+    wrap_after: Option<u32>,
+
+    /// Only wrap at these edges, e.g. `left,right`; the others are walls
+    #[arg(long = "wrap-edges", value_name = "EDGES", value_delimiter = ',')]
+    wrap_edges: Option<Vec<Edge>>,
+
+    /// Walls wrap for this many ticks after the snake starts [default: 0]
+    #[arg(long = "wall-grace", value_name = "TICKS")]
+    wall_grace: Option<u32>,
+
+    /// Peaceful mode: collisions never end the game
+    #[arg(long)]
+    peaceful: bool,
+
+    /// Let the snake pass through its own body (practice mode)
+    #[arg(long = "no-self-collision")]
+    no_self_collision: bool,
+
+    /// Don't count running into the segment right behind the head
+    #[arg(long = "neck-grace")]
+    neck_grace: bool,
+
+    /// Keep the score when restarting instead of starting from zero
+    #[arg(long = "keep-score")]
+    keep_score: bool,
+
     /// Disable colors
     #[arg(long = "no-color")]
     no_color: bool,
 
-    /// Board width
-    #[arg(long, default_value = "30")]
-    width: u16,
+    /// Board width [default: 30]
+    #[arg(long)]
+    width: Option<u16>,
+
+    /// Board height [default: 20]
+    #[arg(long)]
+    height: Option<u16>,
+
+    /// Steer the snake by clicking relative to its head
+    #[arg(long)]
+    mouse: bool,
+
+    /// Show an arrow towards the food and its distance
+    #[arg(long = "food-hint")]
+    food_hint: bool,
+
+    /// Never spawn food in the cell right in front of the snake
+    #[arg(long = "fair-food")]
+    fair_food: bool,
+
+    /// Draw the snake's body as a continuous line
+    #[arg(long = "connected-body")]
+    connected_body: bool,
+
+    /// Draw a faint grid on the board
+    #[arg(long)]
+    grid: bool,
+
+    /// Border style of the panels [default: plain]
+    #[arg(long, value_enum)]
+    border: Option<BorderStyle>,
+
+    /// Leave out the border around the board to save space
+    #[arg(long = "no-game-border")]
+    no_game_border: bool,
+
+    /// Shape of the playfield; cells outside it are walls (non-rectangular boards never wrap) [default: rectangle]
+    #[arg(long, value_enum)]
+    shape: Option<Shape>,
+
+    /// Where to place the board when it doesn't fill its panel [default: center]
+    #[arg(long = "board-anchor", value_enum)]
+    board_anchor: Option<Placement>,
+
+    /// How to write out scores: 1230, 1,230 or 001230 [default: plain]
+    #[arg(long = "score-format", value_enum)]
+    score_format: Option<ScoreStyle>,
+
+    /// Direction the snake starts moving in [default: right]
+    #[arg(long, value_enum)]
+    direction: Option<Heading>,
+
+    /// Milliseconds between game ticks (20-1000)
+    #[arg(long = "tick-ms", value_name = "MS", value_parser = clap::value_parser!(u64).range(20..=1000), conflicts_with = "speed")]
+    tick_ms: Option<u64>,
+
+    /// Game speed from 1 (slow) to 10 (fast)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=10))]
+    speed: Option<u8>,
+
+    /// Lose a point every this many ticks without eating
+    #[arg(long, value_name = "TICKS", value_parser = clap::value_parser!(u32).range(1..))]
+    hunger: Option<u32>,
+
+    /// Show a single status line instead of the stats and controls panels
+    #[arg(long = "hud-minimal")]
+    hud_minimal: bool,
+
+    /// Show the score, length and time played while paused
+    #[arg(long = "pause-stats")]
+    pause_stats: bool,
+
+    /// Color the snake with a moving rainbow
+    #[arg(long)]
+    rainbow: bool,
+
+    /// Replay the last few ticks in slow motion after a crash
+    #[arg(long = "death-replay")]
+    death_replay: bool,
+
+    /// Always show the board as a scaled-down minimap
+    #[arg(long)]
+    minimap: bool,
 
-    /// Board height
-    #[arg(long, default_value = "20")]
-    height: u16,
+    /// Double the points of food eaten within this many ticks of the last [default: 0]
+    #[arg(long, value_name = "TICKS")]
+    combo: Option<u32>,
+
+    /// Show a meter draining as the combo window closes
+    #[arg(long = "combo-meter")]
+    combo_meter: bool,
+
+    /// Mark the cell the snake moves into next
+    #[arg(long)]
+    ghost: bool,
+
+    /// Show a progress bar towards filling the board
+    #[arg(long)]
+    progress: bool,
+
+    /// Keep the snake still at the start of each game until a movement key
+    #[arg(long = "wait-for-input")]
+    wait_for_input: bool,
+
+    /// Start the game paused
+    #[arg(long = "start-paused")]
+    start_paused: bool,
+
+    /// Ticks without food on the board after eating [default: 0]
+    #[arg(long = "respawn-delay", value_name = "TICKS")]
+    respawn_delay: Option<u32>,
+
+    /// Teleport the snake somewhere free on a crash instead of ending the game
+    #[arg(long = "respawn-on-death")]
+    respawn_on_death: bool,
+
+    /// Number of lives; the snake respawns after a crash until they run out [default: 1]
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=9))]
+    lives: Option<u32>,
+
+    /// Stop the snake growing at this length; food still scores
+    #[arg(long = "max-length", value_name = "SEGMENTS", value_parser = clap::value_parser!(u64).range(1..))]
+    max_length: Option<u64>,
+
+    /// Close in the outermost ring of the board every this many ticks [default: 0]
+    #[arg(long, value_name = "TICKS")]
+    shrink: Option<u32>,
+
+    /// Redraw at most this many times a second between game changes
+    #[arg(long = "max-fps", value_name = "FPS", value_parser = clap::value_parser!(u32).range(1..))]
+    max_fps: Option<u32>,
+
+    /// Start a self-playing demo after this many seconds without input
+    #[arg(long, value_name = "SECONDS")]
+    attract: Option<u64>,
+
+    /// Smallest cell size in rows; boards that don't fit at it become a minimap
+    #[arg(long = "min-cell-size", value_name = "ROWS", value_parser = clap::value_parser!(u16).range(1..))]
+    min_cell_size: Option<u16>,
+
+    /// Largest cell size in rows, to keep small boards from filling big terminals
+    #[arg(long = "max-cell-size", value_name = "ROWS", value_parser = clap::value_parser!(u16).range(1..))]
+    max_cell_size: Option<u16>,
+
+    /// Shrink the board to the largest size that fits the terminal
+    #[arg(long)]
+    fit: bool,
+
+    /// JSON file with a snake, food and score to practice from
+    #[arg(long, value_name = "FILE")]
+    scenario: Option<PathBuf>,
+
+    /// JSON file with translated UI text; missing entries stay English
+    #[arg(long, value_name = "FILE")]
+    messages: Option<PathBuf>,
+
+    /// Print a JSON summary of the final game to stdout on exit
+    #[arg(long = "stats-json")]
+    stats_json: bool,
+
+    /// Treat configuration warnings, such as hard to see colors, as errors
+    #[arg(long)]
+    strict: bool,
+
+    /// Play today's daily challenge (same food sequence for everyone, based on the UTC date)
+    #[arg(long)]
+    daily: bool,
+
+    /// Start from a saved profile; environment variables and other flags still override it
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Save the effective settings as a named profile
+    #[arg(long = "save-profile", value_name = "NAME")]
+    save_profile: Option<String>,
+}
+
+/// Settings used when no profile is given.
+fn default_config() -> GameConfig {
+    GameConfig::new(30, 20) // Board size: 30x20
+        .with_snake_color(Color::Green) // Snake color
+        .with_food_color(Color::LightRed) // Food color
+        .with_wall_color(Color::LightGreen) // Wall color
+        .with_border_color(Color::LightCyan)
+        .with_background_color(Color::Black) // Background color
+}
+
+/// Builds the game settings from the profile, the environment and command
+/// line flags.
+fn config_from_args(args: &Args) -> io::Result<GameConfig> {
+    // Precedence: defaults < profile < environment (SNAKE_*) < CLI flags
+    let mut config = match &args.profile {
+        Some(name) => GameConfig::load_profile(name)?,
+        None => default_config(),
+    };
+    config.apply_env();
+
+    if let Some(width) = args.width {
+        config.board_width = width;
+    }
+    if let Some(height) = args.height {
+        config.board_height = height;
+    }
+    if args.no_sound {
+        config.enable_sound = false; // Disable console bell sounds based on CLI argument
+    }
+    if args.no_color {
+        config.enable_colors = false; // Disable colors based on CLI argument
+    }
+    if args.solid_walls {
+        config.wall_wrapping = false; // Disable wall wrapping based on CLI argument
+    }
+
+    // Solid walls are drawn red, unless a profile or theme picked a color
+    if !config.wall_wrapping && config.wall_color == Color::LightGreen {
+        config.wall_color = Color::Red;
+    }
+
+    // Switches only ever turn their feature on, so leaving one out keeps the
+    // profile's or environment's choice
+    if args.peaceful {
+        config = config.with_peaceful(true);
+    }
+    if args.no_self_collision {
+        config = config.with_self_collision(false);
+    }
+    if args.neck_grace {
+        config = config.with_neck_grace(true);
+    }
+    if args.keep_score {
+        config = config.with_keep_score_on_restart(true);
+    }
+    if args.mouse {
+        config = config.with_mouse_control(true);
+    }
+    if args.no_game_border {
+        config = config.with_game_border(false);
+    }
+    if args.grid {
+        config = config.with_grid(true);
+    }
+    if args.minimap {
+        config = config.with_minimap(true);
+    }
+    if args.hud_minimal {
+        config = config.with_hud_minimal(true);
+    }
+    if args.pause_stats {
+        config = config.with_pause_stats(true);
+    }
+    if args.food_hint {
+        config = config.with_food_hint(true);
+    }
+    if args.fair_food {
+        config = config.with_food_avoids_next_head(true);
+    }
+    if args.connected_body {
+        config = config.with_connected_body(true);
+    }
+    if args.rainbow {
+        config = config.with_rainbow(true);
+    }
+    if args.death_replay {
+        config = config.with_death_replay(true);
+    }
+    if args.respawn_on_death {
+        config = config.with_respawn_on_death(true);
+    }
+    if args.combo_meter {
+        config = config.with_combo_meter(true);
+    }
+    if args.start_paused {
+        config = config.with_start_paused(true);
+    }
+    if args.wait_for_input {
+        config = config.with_wait_for_input(true);
+    }
+    if args.progress {
+        config = config.with_progress_bar(true);
+    }
+    if args.ghost {
+        config = config.with_ghost_preview(true);
+    }
+
+    if let Some(score) = args.wrap_after {
+        config = config.with_wrap_after_score(Some(score));
+    }
+    if let Some(ticks) = args.wall_grace {
+        config = config.with_wall_grace_ticks(ticks);
+    }
+    if let Some(border) = args.border {
+        config = config.with_border_type(border.into());
+    }
+    if let Some(delay) = args.respawn_delay {
+        config = config.with_respawn_delay(delay);
+    }
+    if let Some(interval) = args.shrink {
+        config = config.with_shrink_interval(interval);
+    }
+    if let Some(lives) = args.lives {
+        config = config.with_lives(lives);
+    }
+    if let Some(length) = args.max_length {
+        config = config.with_max_length(Some(length as usize));
+    }
+    if let Some(window) = args.combo {
+        let multiplier = config.combo_multiplier;
+        config = config.with_combo(window, multiplier);
+    }
+    if let Some(shape) = args.shape {
+        config = config.with_board_shape(shape.into());
+    }
+    if let Some(anchor) = args.board_anchor {
+        config = config.with_board_anchor(anchor.into());
+    }
+    if let Some(format) = args.score_format {
+        config = config.with_score_format(format.into());
+    }
+    if let Some(direction) = args.direction {
+        config = config.with_initial_direction(direction.into());
+    }
+    if let Some(seconds) = args.attract {
+        config = config.with_attract_mode(Some(Duration::from_secs(seconds)));
+    }
+
+    if let Some(ms) = args.tick_ms {
+        config = config.with_tick_ms(ms);
+    }
+    if let Some(level) = args.speed {
+        config = config.with_speed(level);
+    }
+
+    if let Some(edges) = &args.wrap_edges {
+        config = config.with_wrap_edges(WrapEdges {
+            top: edges.contains(&Edge::Top),
+            bottom: edges.contains(&Edge::Bottom),
+            left: edges.contains(&Edge::Left),
+            right: edges.contains(&Edge::Right),
+        });
+    }
+
+    if let Some(fps) = args.max_fps {
+        config = config.with_max_fps(fps);
+    }
+
+    if let Some(size) = args.min_cell_size {
+        config = config.with_min_cell_size(size);
+    }
+
+    if let Some(size) = args.max_cell_size {
+        config = config.with_max_cell_size(size);
+    }
+
+    if let Some(rate) = args.hunger {
+        config = config.with_hunger(rate);
+    }
+
+    // Fit last, once the flags deciding the borders and panels are in
+    if args.fit {
+        let (terminal_width, terminal_height) = crossterm::terminal::size()?;
+        (config.board_width, config.board_height) =
+            renderer::fit_board_to_terminal(&config, terminal_width, terminal_height);
+    }
+
+    if let Some(path) = &args.scenario {
+        config = config.with_scenario(Scenario::load(path)?);
+    }
+
+    if let Some(path) = &args.messages {
+        config = config.with_messages(Messages::load(path)?);
+    }
+
+    Ok(config)
 }
 
 fn main() -> Result<(), io::Error> {
     // Configure the game - you can modify these settings
     let args = Args::parse();
 
-    let wall_wrapping = !args.solid_walls; // Enable or disable wall wrapping based on CLI argument
+    let mut config = config_from_args(&args)?;
 
-    let config = GameConfig::new(args.width, args.height) // Board size: 30x20
-        .with_sound(!args.no_sound) // Enable or disable console bell sounds based on CLI argument
-        .with_colors(!args.no_color) // Enable or disable colors based on CLI argument
-        .with_wall_wrapping(wall_wrapping)
-        .with_snake_color(Color::Green) // Snake color
-        .with_wall_color(if wall_wrapping {
-            Color::LightGreen
-        } else {
-            Color::Red
-        }) // Wall color
-        .with_food_color(Color::LightRed) // Food color
-        .with_border_color(Color::LightCyan)
-        .with_background_color(Color::Black); // Background color
+    if let Some(name) = &args.save_profile {
+        let path = config.save_profile(name)?;
+        eprintln!("Saved profile {:?} to {}", name, path.display());
+    }
+
+    // Per-board-size high scores
+    config = config.with_high_score_file(high_scores::default_high_score_path());
+
+    if args.daily {
+        config = config.with_daily_challenge(Date::today_utc());
+    }
+
+    if let Err(err) = config.validate_colors() {
+        if args.strict {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, err));
+        }
+        eprintln!("warning: {}", err);
+    }
 
     // Create dependencies
-    let input_handler = CrosstermInputHandler::new();
+    let input_handler = CrosstermInputHandler::new()
+        .with_mouse(config.mouse_control)
+        .with_key_map(config.key_map.clone());
     let sound_system = ConsoleSoundSystem::new(config.enable_sound);
 
     // Create and configure the application
-    let mut app = App::new(config, input_handler, sound_system);
+    let mut app = App::new(config, input_handler, sound_system, TuiRenderer::new());
 
     // Setup terminal
     let mut terminal = setup_terminal()?;
@@ -71,8 +592,12 @@ fn main() -> Result<(), io::Error> {
     // Restore terminal
     restore_terminal()?;
 
-    // Print final message
-    println!("Thanks for playing Snake!");
+    // Print final message, or machine-readable stats for scripts
+    if args.stats_json {
+        println!("{}", app.summary().to_json());
+    } else {
+        println!("Thanks for playing Snake!");
+    }
 
     result
 }
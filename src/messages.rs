@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::Path};
+
+/// Every piece of UI text the renderer shows, so the game can be translated.
+///
+/// Locale files are JSON objects with any subset of these fields; missing
+/// entries fall back to English. The `*_hint` entries are templates where
+/// `{key}` stands for the currently bound key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Messages {
+    pub stats_title: String,
+    pub controls_title: String,
+    pub score: String,
+    pub best: String,
+    pub length: String,
+    pub longest: String,
+    pub wraps: String,
+    pub lives: String,
+    pub combo: String,
+    pub best_time: String,
+    pub time: String,
+    pub theme: String,
+    pub speed: String,
+    pub speed_unit: String,
+    pub stamina: String,
+    pub daily_challenge: String,
+    pub food: String,
+    pub control_move: String,
+    pub control_pause: String,
+    pub control_sprint: String,
+    pub control_restart: String,
+    pub control_snapshot: String,
+    pub control_walls: String,
+    pub control_theme: String,
+    pub control_quit: String,
+    pub peaceful_note: String,
+    pub paused: String,
+    pub resume_hint: String,
+    pub game_over: String,
+    pub you_win: String,
+    pub final_score: String,
+    pub restart_hint: String,
+    pub quit_hint: String,
+    pub too_small: String,
+    pub minimum_size: String,
+    pub current_size: String,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self {
+            stats_title: String::from("Stats"),
+            controls_title: String::from("Controls"),
+            score: String::from("Score"),
+            best: String::from("Best"),
+            length: String::from("Length"),
+            longest: String::from("Longest"),
+            wraps: String::from("Wraps"),
+            lives: String::from("Lives"),
+            combo: String::from("Combo"),
+            best_time: String::from("Best Time"),
+            time: String::from("Time"),
+            theme: String::from("Theme"),
+            speed: String::from("Speed"),
+            speed_unit: String::from("cells/s"),
+            stamina: String::from("Stamina"),
+            daily_challenge: String::from("Daily Challenge"),
+            food: String::from("Food"),
+            control_move: String::from("Move"),
+            control_pause: String::from("Pause/Resume"),
+            control_sprint: String::from("Toggle Sprint"),
+            control_restart: String::from("Restart"),
+            control_snapshot: String::from("Save Snapshot"),
+            control_walls: String::from("Toggle Walls"),
+            control_theme: String::from("Cycle Theme"),
+            control_quit: String::from("Quit"),
+            peaceful_note: String::from("Peaceful mode: no deaths"),
+            paused: String::from("PAUSED"),
+            resume_hint: String::from("Press {key} to resume"),
+            game_over: String::from("GAME OVER"),
+            you_win: String::from("YOU WIN!"),
+            final_score: String::from("Final Score"),
+            restart_hint: String::from("Press {key} to restart"),
+            quit_hint: String::from("Press {key} to quit"),
+            too_small: String::from("Terminal too small!"),
+            minimum_size: String::from("Minimum size"),
+            current_size: String::from("Current size"),
+        }
+    }
+}
+
+impl Messages {
+    /// Loads a locale file, filling in English for any missing entries.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(io::Error::other)
+    }
+}
+
+/// Fills the `{key}` placeholder of a hint template with a key label.
+pub fn fill_key(template: &str, key: &str) -> String {
+    template.replace("{key}", key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_locale_falls_back_to_english() {
+        let path = std::env::temp_dir().join(format!(
+            "snake_rs_messages_test_{}.json",
+            std::process::id()
+        ));
+        fs::write(&path, r#"{ "paused": "PAUSA", "score": "Punteggio" }"#).unwrap();
+
+        let messages = Messages::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(messages.paused, "PAUSA");
+        assert_eq!(messages.score, "Punteggio");
+        assert_eq!(messages.game_over, Messages::default().game_over);
+    }
+
+    #[test]
+    fn test_hint_template_takes_bound_key() {
+        let messages = Messages::default();
+        assert_eq!(
+            fill_key(&messages.restart_hint, "F5"),
+            "Press F5 to restart"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUpKind {
+    /// Lengthens the tick interval, slowing the snake down.
+    Slow,
+    /// Shortens the tick interval, speeding the snake up.
+    Fast,
+}
+
+impl PowerUpKind {
+    fn scale(&self, tick_rate: Duration) -> Duration {
+        match self {
+            PowerUpKind::Slow => tick_rate * 2,
+            PowerUpKind::Fast => tick_rate / 2,
+        }
+    }
+}
+
+/// Temporary change to the tick rate caused by eating a power-up.
+///
+/// Only one effect is active at a time: activating a new one replaces the
+/// current effect and its remaining duration.
+#[derive(Debug, Default)]
+pub struct SpeedEffect {
+    active: Option<(PowerUpKind, u32)>,
+}
+
+impl SpeedEffect {
+    pub fn new() -> Self {
+        Self { active: None }
+    }
+
+    pub fn activate(&mut self, kind: PowerUpKind, ticks: u32) {
+        self.active = (ticks > 0).then_some((kind, ticks));
+    }
+
+    pub fn active(&self) -> Option<PowerUpKind> {
+        self.active.map(|(kind, _)| kind)
+    }
+
+    /// The tick interval to use given the unmodified `base` interval.
+    pub fn tick_rate(&self, base: Duration) -> Duration {
+        match self.active {
+            Some((kind, _)) => kind.scale(base),
+            None => base,
+        }
+    }
+
+    /// Counts down one tick, clearing the effect once it runs out.
+    pub fn tick(&mut self) {
+        if let Some((kind, ticks_remaining)) = self.active {
+            self.active = (ticks_remaining > 1).then_some((kind, ticks_remaining - 1));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.active = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Direction, Game, GameEvent, Position};
+
+    #[test]
+    fn test_slow_power_up_lengthens_tick_then_restores() {
+        let base = Duration::from_millis(100);
+        let mut game = Game::with_seed(20, 20, 1);
+        let head = game.snake().head();
+        game.set_direction(Direction::Down);
+        game.place_power_up(Position::new(head.x, head.y + 1), PowerUpKind::Slow);
+
+        let mut effect = SpeedEffect::new();
+        let event = game.update();
+        assert_eq!(event, GameEvent::PowerUp(PowerUpKind::Slow));
+        effect.activate(PowerUpKind::Slow, 3);
+
+        for _ in 0..3 {
+            assert_eq!(effect.tick_rate(base), Duration::from_millis(200));
+            effect.tick();
+        }
+        assert_eq!(effect.tick_rate(base), base);
+        assert_eq!(effect.active(), None);
+    }
+
+    #[test]
+    fn test_latest_power_up_wins() {
+        let base = Duration::from_millis(100);
+        let mut effect = SpeedEffect::new();
+
+        effect.activate(PowerUpKind::Slow, 5);
+        effect.activate(PowerUpKind::Fast, 2);
+
+        assert_eq!(effect.tick_rate(base), Duration::from_millis(50));
+        effect.tick();
+        effect.tick();
+        assert_eq!(effect.tick_rate(base), base);
+    }
+}
@@ -1,16 +1,475 @@
 use crate::config::GameConfig;
-use crate::game::{Game, GameState};
+use crate::game::{
+    BoardShape, Direction as Heading, FoodKind, Game, GameState, Position, WrapEdges,
+};
+use crate::input::InputAction;
+use crate::messages::fill_key;
+use crate::power_up::PowerUpKind;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols::border,
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, Gauge, Paragraph},
 };
+use std::{cmp::Ordering, collections::VecDeque, time::Duration};
+
+/// Colors the victory border cycles through.
+const WIN_COLORS: [Color; 5] = [
+    Color::Yellow,
+    Color::LightGreen,
+    Color::LightCyan,
+    Color::LightMagenta,
+    Color::LightRed,
+];
+
+/// Number of ticks the victory border keeps cycling colors.
+const WIN_ANIMATION_FRAMES: u32 = 30;
+
+/// Width of the stats/controls panel to the right of the game area.
+const SIDE_PANEL_WIDTH: u16 = 25;
+
+/// Board border with the wrapping edges dashed, so they read as portals
+/// rather than walls.
+fn portal_border(border_type: BorderType, edges: WrapEdges) -> border::Set {
+    let mut set = border_type.to_border_set();
+    if edges.top {
+        set.horizontal_top = "╌";
+    }
+    if edges.bottom {
+        set.horizontal_bottom = "╌";
+    }
+    if edges.left {
+        set.vertical_left = "┆";
+    }
+    if edges.right {
+        set.vertical_right = "┆";
+    }
+    set
+}
+
+/// Largest board that fits a terminal of the given size, never exceeding the
+/// board `config` asks for. Accounts for the side panel or status line, the
+/// outer border, the game border if it's drawn and each cell being two
+/// columns wide.
+pub fn fit_board_to_terminal(
+    config: &GameConfig,
+    terminal_width: u16,
+    terminal_height: u16,
+) -> (u16, u16) {
+    let game_area = main_chunks(config, Rect::new(0, 0, terminal_width, terminal_height))[0];
+    // Outer block border (+2) and game board border on each axis
+    let border = 2 + game_border_size(config);
+    let available_width = game_area.width.saturating_sub(border);
+    let available_height = game_area.height.saturating_sub(border);
+
+    let max_width = (available_width / 2).max(1);
+    let max_height = available_height.max(1);
+
+    (
+        config.board_width.min(max_width),
+        config.board_height.min(max_height),
+    )
+}
+
+/// Rows and columns the game border takes up on each axis.
+fn game_border_size(config: &GameConfig) -> u16 {
+    if config.game_border { 2 } else { 0 }
+}
+
+/// Screen geometry of the game board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardLayout {
+    /// Board area including its border, if it has one.
+    pub area: Rect,
+    /// Area inside the border where cells are drawn.
+    pub inner: Rect,
+    /// Height of a cell in rows; cells are twice as wide in columns.
+    pub cell_size: u16,
+}
+
+impl BoardLayout {
+    /// Fits the board into `available`, the area inside the outer block.
+    /// Returns `None` if the area is too small to hold the board at the
+    /// configured minimum cell size.
+    pub fn compute(
+        config: &GameConfig,
+        (board_width, board_height): (u16, u16),
+        available: Rect,
+    ) -> Option<Self> {
+        // Calculate minimum space needed for the game board (plus border)
+        let border = game_border_size(config);
+        let min_width = board_width * 2 + border;
+        let min_height = board_height + border;
+
+        // Check if terminal is too small
+        if available.width < min_width || available.height < min_height {
+            return None;
+        }
+
+        // Calculate optimal cell size that fits within available space,
+        // leaving room for the border
+        let max_cell_width = (available.width - border) / 2 / board_width;
+        let max_cell_height = (available.height - border) / board_height;
+
+        // Use the smaller dimension to maintain aspect ratio and ensure everything fits
+        let fit_size = std::cmp::min(max_cell_width, max_cell_height);
+        let fit_size = std::cmp::max(1, fit_size); // Minimum of 1
+
+        // Apply the configured zoom limits; a minimum that doesn't fit leaves
+        // the board to the minimap
+        let mut cell_size = fit_size;
+        if let Some(max_cell_size) = config.max_cell_size {
+            cell_size = cell_size.min(max_cell_size);
+        }
+        cell_size = cell_size.max(config.min_cell_size);
+        if cell_size > fit_size {
+            return None;
+        }
+
+        // Calculate the exact game board dimensions
+        let game_width = board_width * cell_size * 2;
+        let game_height = board_height * cell_size;
+
+        // Place the game board within the available outer inner area
+        let (offset_x, offset_y) = config.board_anchor.offset(
+            available.width.saturating_sub(game_width + border),
+            available.height.saturating_sub(game_height + border),
+        );
+
+        // Create the game board area with border
+        let area = Rect::new(
+            available.x + offset_x,
+            available.y + offset_y,
+            game_width + border,
+            game_height + border,
+        );
+        let inner = if config.game_border {
+            Block::default().borders(Borders::ALL).inner(area)
+        } else {
+            area
+        };
+
+        Some(Self {
+            area,
+            inner,
+            cell_size,
+        })
+    }
+
+    /// Locates the board given the full terminal area, matching `draw_frame`.
+    pub fn for_frame(
+        config: &GameConfig,
+        dimensions: (u16, u16),
+        frame_area: Rect,
+    ) -> Option<Self> {
+        let game_area = main_chunks(config, frame_area)[0];
+        let outer_inner = Block::default().borders(Borders::ALL).inner(game_area);
+        Self::compute(config, dimensions, outer_inner)
+    }
+
+    /// Screen area of the board cell at `pos`.
+    pub fn cell_rect(&self, pos: Position) -> Rect {
+        Rect::new(
+            self.inner.x + (pos.x * self.cell_size * 2),
+            self.inner.y + (pos.y * self.cell_size),
+            self.cell_size,
+            self.cell_size,
+        )
+    }
+}
+
+/// Where the scaled-down board goes when the full board doesn't fit: one
+/// character covers a block of `scale_x` by `scale_y` board cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MinimapLayout {
+    area: Rect,
+    inner: Rect,
+    scale_x: u16,
+    scale_y: u16,
+}
+
+impl MinimapLayout {
+    /// Scales the board down to fit `available`, placed at the configured
+    /// anchor, or `None` if not even the border fits.
+    fn compute(
+        config: &GameConfig,
+        (board_width, board_height): (u16, u16),
+        available: Rect,
+    ) -> Option<Self> {
+        let max_width = available.width.saturating_sub(2);
+        let max_height = available.height.saturating_sub(2);
+        if max_width == 0 || max_height == 0 || board_width == 0 || board_height == 0 {
+            return None;
+        }
+
+        let scale_x = board_width.div_ceil(max_width);
+        let scale_y = board_height.div_ceil(max_height);
+        let width = board_width.div_ceil(scale_x) + 2;
+        let height = board_height.div_ceil(scale_y) + 2;
+        let (offset_x, offset_y) = config
+            .board_anchor
+            .offset(available.width - width, available.height - height);
+        let area = Rect::new(
+            available.x + offset_x,
+            available.y + offset_y,
+            width,
+            height,
+        );
+
+        Some(Self {
+            area,
+            inner: Block::default().borders(Borders::ALL).inner(area),
+            scale_x,
+            scale_y,
+        })
+    }
+
+    /// Screen position of the character covering board cell `pos`.
+    fn cell_position(&self, pos: Position) -> (u16, u16) {
+        (
+            self.inner.x + pos.x / self.scale_x,
+            self.inner.y + pos.y / self.scale_y,
+        )
+    }
+}
+
+/// Minimap glyph for a block with `count` of its `cells` taken by the snake.
+fn density_glyph(count: u32, cells: u32) -> &'static str {
+    match count * 4 / cells.max(1) {
+        0 => "░",
+        1 => "▒",
+        2 => "▓",
+        _ => "█",
+    }
+}
+
+/// Splits the terminal into the game area and the stats area: a side panel,
+/// or a single status line below the board with the minimal HUD.
+fn main_chunks(config: &GameConfig, area: Rect) -> std::rc::Rc<[Rect]> {
+    if config.hud_minimal {
+        return Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // Game area
+                Constraint::Length(1), // Status line
+            ])
+            .split(area);
+    }
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Min(50),                  // Game area
+            Constraint::Length(SIDE_PANEL_WIDTH), // Side panel
+        ])
+        .split(area)
+}
+
+/// Restart hint for the end-of-game popups, naming the bound key.
+fn restart_hint(config: &GameConfig) -> String {
+    fill_key(
+        &config.messages.restart_hint,
+        &config.key_map.hint(InputAction::Restart),
+    )
+}
+
+/// Quit hint for the end-of-game popups, naming the bound key.
+fn quit_hint(config: &GameConfig) -> String {
+    fill_key(
+        &config.messages.quit_hint,
+        &config.key_map.hint(InputAction::Quit),
+    )
+}
+
+/// Faint color for the grid that stays close to the background.
+fn grid_color(background: Color) -> Color {
+    match background {
+        Color::Black | Color::Reset => Color::DarkGray,
+        Color::White | Color::Gray => Color::Gray,
+        _ => Color::DarkGray,
+    }
+}
+
+/// Arrow pointing from `head` towards `food` in one of eight directions, or
+/// `None` if they share a cell.
+fn food_hint_arrow(head: Position, food: Position) -> Option<&'static str> {
+    let arrow = match (food.x.cmp(&head.x), food.y.cmp(&head.y)) {
+        (Ordering::Less, Ordering::Less) => "↖",
+        (Ordering::Equal, Ordering::Less) => "↑",
+        (Ordering::Greater, Ordering::Less) => "↗",
+        (Ordering::Less, Ordering::Equal) => "←",
+        (Ordering::Equal, Ordering::Equal) => return None,
+        (Ordering::Greater, Ordering::Equal) => "→",
+        (Ordering::Less, Ordering::Greater) => "↙",
+        (Ordering::Equal, Ordering::Greater) => "↓",
+        (Ordering::Greater, Ordering::Greater) => "↘",
+    };
+    Some(arrow)
+}
+
+/// Box-drawing glyph for a body segment at `segment` joining the segments
+/// before and after it. Neighbors that aren't adjacent, such as across a
+/// wrapped edge, get no connector; a segment with none falls back to `○`.
+fn body_glyph(segment: Position, neighbors: [Option<Position>; 2]) -> &'static str {
+    let (mut up, mut down, mut left, mut right) = (false, false, false, false);
+    for neighbor in neighbors.into_iter().flatten() {
+        let dx = i32::from(neighbor.x) - i32::from(segment.x);
+        let dy = i32::from(neighbor.y) - i32::from(segment.y);
+        match (dx, dy) {
+            (0, -1) => up = true,
+            (0, 1) => down = true,
+            (-1, 0) => left = true,
+            (1, 0) => right = true,
+            _ => {}
+        }
+    }
+
+    match (up, down, left, right) {
+        (true, true, _, _) => "│",
+        (_, _, true, true) => "─",
+        (false, true, false, true) => "┌",
+        (false, true, true, false) => "┐",
+        (true, false, false, true) => "└",
+        (true, false, true, false) => "┘",
+        (true, false, false, false) | (false, true, false, false) => "│",
+        (false, false, true, false) | (false, false, false, true) => "─",
+        _ => "○",
+    }
+}
+
+/// Degrees of hue between neighboring segments, and per frame, in rainbow
+/// mode.
+const RAINBOW_HUE_STEP: u64 = 15;
+
+/// Rainbow color of the segment at `index` on `frame`. The hue moves along
+/// the body as frames advance.
+fn rainbow_color(index: usize, frame: u64) -> Color {
+    let hue = (index as u64 + frame) * RAINBOW_HUE_STEP % 360;
+    // Fully saturated, full brightness HSV to RGB
+    let sector = hue / 60;
+    let rising = ((hue % 60) * 255 / 60) as u8;
+    let falling = 255 - rising;
+    let (r, g, b) = match sector {
+        0 => (255, rising, 0),
+        1 => (falling, 255, 0),
+        2 => (0, 255, rising),
+        3 => (0, falling, 255),
+        4 => (rising, 0, 255),
+        _ => (255, 0, falling),
+    };
+    Color::Rgb(r, g, b)
+}
+
+/// Fraction of the way from nothing to `goal`, capped at 1.
+fn progress_ratio(length: usize, goal: usize) -> f64 {
+    if goal == 0 {
+        return 1.0;
+    }
+    (length as f64 / goal as f64).min(1.0)
+}
+
+/// How much of the combo window is left, from 1 right after a meal down to
+/// 0 once it has closed.
+fn combo_meter_ratio(game: &Game) -> f64 {
+    match game.combo_window() {
+        0 => 0.0,
+        window => f64::from(game.combo_ticks_left()) / f64::from(window),
+    }
+}
+
+/// Whether the stats panel has a combo meter row.
+fn shows_combo_meter(game: &Game, config: &GameConfig) -> bool {
+    config.combo_meter && game.combo_window() > 0
+}
+
+/// Stats panel line with a `label` in the border color and a bold `value` in
+/// `color`, or in white without colors.
+fn stat_line(config: &GameConfig, label: &str, value: String, color: Color) -> Line<'static> {
+    let (label_color, value_color) = if config.enable_colors {
+        (config.border_color, color)
+    } else {
+        (Color::White, Color::White)
+    };
+
+    Line::from(vec![
+        Span::styled(format!("{}: ", label), Style::default().fg(label_color)),
+        Span::styled(
+            value,
+            Style::default()
+                .fg(value_color)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ])
+}
+
+/// Style for food that expires, shifting from green through yellow to red as
+/// `ticks_remaining` of its `total` lifetime run out. Without colors the
+/// plain style is used, so only the glyph shows.
+pub fn timed_food_style(config: &GameConfig, ticks_remaining: u32, total: u32) -> Style {
+    if !config.enable_colors {
+        return Style::default();
+    }
+
+    let left = if total == 0 {
+        0.0
+    } else {
+        (f64::from(ticks_remaining) / f64::from(total)).min(1.0)
+    };
+    // Red rises over the first half of the countdown, green fades over the second
+    let red = (2.0 * (1.0 - left)).min(1.0);
+    let green = (2.0 * left).min(1.0);
+    Style::default().fg(Color::Rgb(
+        (red * 255.0).round() as u8,
+        (green * 255.0).round() as u8,
+        0,
+    ))
+}
+
+/// Per-frame state owned by the app loop rather than by `Game` or `GameConfig`.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameContext<'a> {
+    /// Achievement toast to show on top of everything else.
+    pub toast: Option<&'a str>,
+    /// Current effective tick interval.
+    pub tick_rate: Duration,
+    /// Ticks since the game was won, driving the victory animation.
+    pub win_frame: u32,
+    /// Ticks since the app started, driving looping animations.
+    pub tick: u64,
+    /// Frames drawn since the app started. Unlike `tick` this keeps counting
+    /// while the game is paused or over, so it drives overlay animations.
+    pub frame: u64,
+    /// Remaining sprint stamina from 0.0 to 1.0.
+    pub stamina: f64,
+    /// Time spent playing the current game.
+    pub time_survived: Duration,
+    /// Earlier body to draw instead of the snake while a crash is replayed.
+    pub replay_snake: Option<&'a VecDeque<Position>>,
+}
+
+impl Default for FrameContext<'_> {
+    fn default() -> Self {
+        Self {
+            toast: None,
+            tick_rate: Duration::from_millis(100),
+            win_frame: 0,
+            tick: 0,
+            frame: 0,
+            stamina: 1.0,
+            time_survived: Duration::ZERO,
+            replay_snake: None,
+        }
+    }
+}
 
 pub trait Renderer {
     type Error;
 
+    /// Draws one complete frame of the game into `f`.
+    fn draw(&mut self, f: &mut Frame, game: &Game, config: &GameConfig, context: &FrameContext);
+
     #[allow(dead_code)]
     fn render(&mut self, game: &Game, config: &GameConfig) -> Result<(), Self::Error>;
     #[allow(dead_code)]
@@ -30,74 +489,277 @@ impl TuiRenderer {
         Self
     }
 
-    fn render_game_area(&self, f: &mut Frame, game: &Game, config: &GameConfig, area: Rect) {
+    /// Draws the whole board scaled down into `minimap`, shading each block
+    /// by how much of it the snake fills, with the food and head on top.
+    fn render_minimap(
+        &self,
+        f: &mut Frame,
+        game: &Game,
+        config: &GameConfig,
+        minimap: &MinimapLayout,
+    ) {
+        let style = |color| {
+            if config.enable_colors {
+                Style::default().fg(color)
+            } else {
+                Style::default()
+            }
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(config.border_type)
+            .border_style(style(config.wall_color));
+        f.render_widget(block, minimap.area);
+
+        let inner = minimap.inner;
+        let mut counts = vec![0u32; usize::from(inner.width) * usize::from(inner.height)];
+        for &segment in game.snake().body() {
+            let (x, y) = minimap.cell_position(segment);
+            if inner.contains((x, y).into()) {
+                counts[usize::from(y - inner.y) * usize::from(inner.width)
+                    + usize::from(x - inner.x)] += 1;
+            }
+        }
+
+        let buffer = f.buffer_mut();
+        let cells = u32::from(minimap.scale_x) * u32::from(minimap.scale_y);
+        for (i, &count) in counts.iter().enumerate().filter(|(_, count)| **count > 0) {
+            let x = inner.x + (i % usize::from(inner.width)) as u16;
+            let y = inner.y + (i / usize::from(inner.width)) as u16;
+            buffer[(x, y)]
+                .set_symbol(density_glyph(count, cells))
+                .set_style(style(config.snake_color));
+        }
+
+        let mut mark = |pos: Position, symbol: &str, style: Style| {
+            let (x, y) = minimap.cell_position(pos);
+            if inner.contains((x, y).into()) {
+                buffer[(x, y)].set_symbol(symbol).set_style(style);
+            }
+        };
+        if let Some(food) = game.food() {
+            mark(food, "◆", style(config.food_color));
+        }
+        if let Some((top_left, _)) = game.big_food() {
+            mark(top_left, "◆", style(config.food_color));
+        }
+        mark(
+            game.snake().head(),
+            "●",
+            style(config.snake_color).add_modifier(Modifier::BOLD),
+        );
+    }
+
+    fn render_game_area(
+        &self,
+        f: &mut Frame,
+        game: &Game,
+        config: &GameConfig,
+        context: &FrameContext,
+        area: Rect,
+    ) {
+        // The game owns the board size, which can differ from the config
+        let (board_width, board_height) = game.dimensions();
         let border_style = if config.enable_colors {
             Style::default().fg(config.border_color)
         } else {
             Style::default()
         };
-        let outer_block = Block::default()
-            .title("Snake Game")
+        let mut outer_block = Block::default()
             .borders(Borders::ALL)
+            .border_type(config.border_type)
             .style(border_style);
+        // Keep the state readable at a glance, even with the overlay off-center
+        let status = match game.state() {
+            GameState::Paused => Some(&config.messages.paused),
+            GameState::GameOver => Some(&config.messages.game_over),
+            GameState::Playing | GameState::Won => None,
+        };
+        let title = match status {
+            Some(status) if config.title.is_empty() => format!("[{status}]"),
+            Some(status) => format!("{} [{status}]", config.title),
+            None => config.title.clone(),
+        };
+        if !title.is_empty() {
+            outer_block = outer_block.title(title);
+        }
 
         let outer_inner = outer_block.inner(area);
         f.render_widget(outer_block, area);
 
-        // Calculate minimum space needed for the game board (plus border)
-        let min_width = config.board_width * 2 + 2; // +2 for border
-        let min_height = config.board_height + 2; // +2 for border
+        let layout = if config.minimap {
+            None
+        } else {
+            BoardLayout::compute(config, game.dimensions(), outer_inner)
+        };
+        let Some(layout) = layout else {
+            // Keep the game playable on a scaled-down board
+            if let Some(minimap) = MinimapLayout::compute(config, game.dimensions(), outer_inner) {
+                self.render_minimap(f, game, config, &minimap);
+                return;
+            }
+
+            // Calculate minimum space needed for the game board (plus border)
+            let border = game_border_size(config);
+            let min_width = board_width * 2 + border;
+            let min_height = board_height + border;
 
-        // Check if terminal is too small
-        if outer_inner.width < min_width || outer_inner.height < min_height {
             let message = format!(
-                "Terminal too small!\nMinimum size: {}x{}\nCurrent size: {}x{}",
-                min_width, min_height, outer_inner.width, outer_inner.height
+                "{}\n{}: {}x{}\n{}: {}x{}",
+                config.messages.too_small,
+                config.messages.minimum_size,
+                min_width,
+                min_height,
+                config.messages.current_size,
+                outer_inner.width,
+                outer_inner.height
             );
             let warning_paragraph = Paragraph::new(message)
                 .style(Style::default().fg(Color::Red))
                 .alignment(Alignment::Center);
             f.render_widget(warning_paragraph, outer_inner);
             return;
-        }
-
-        // Calculate optimal cell size that fits within available space
-        let max_cell_width = outer_inner.width / 2 / config.board_width;
-        let max_cell_height = outer_inner.height / config.board_height;
-
-        // Use the smaller dimension to maintain aspect ratio and ensure everything fits
-        let cell_size = std::cmp::min(max_cell_width, max_cell_height);
-        let cell_size = std::cmp::max(1, cell_size); // Minimum of 1
-
-        // Calculate the exact game board dimensions
-        let game_width = config.board_width * cell_size * 2;
-        let game_height = config.board_height * cell_size;
-
-        // Center the game board within the available outer inner area
-        let offset_x = (outer_inner.width.saturating_sub(game_width + 2)) / 2; // +2 for game border
-        let offset_y = (outer_inner.height.saturating_sub(game_height + 2)) / 2; // +2 for game border
-
-        // Create the game board area with border
-        let game_board_area = Rect::new(
-            outer_inner.x + offset_x,
-            outer_inner.y + offset_y,
-            game_width + 2,
-            game_height + 2,
-        );
+        };
 
         // Draw the game board border
+        let wall_color =
+            if config.wrap_after_score.is_some() || game.wall_wrapping() != config.wall_wrapping {
+                // Walls that unlock or were toggled show whether they're passable
+                if game.is_wrapping() {
+                    Color::LightGreen
+                } else {
+                    Color::Red
+                }
+            } else {
+                config.wall_color
+            };
         let wall_style = if config.enable_colors {
-            Style::default().fg(config.wall_color)
+            Style::default().fg(wall_color)
         } else {
             Style::default()
         };
 
-        let game_block = Block::default()
+        let mut game_block = Block::default()
             .borders(Borders::ALL)
+            .border_type(config.border_type)
             .border_style(wall_style);
+        // Follows the game rather than the config, so toggling walls shows
+        if game.is_wrapping() {
+            let edges = WrapEdges {
+                top: game.wraps_towards(Heading::Up),
+                bottom: game.wraps_towards(Heading::Down),
+                left: game.wraps_towards(Heading::Left),
+                right: game.wraps_towards(Heading::Right),
+            };
+            game_block = game_block.border_set(portal_border(config.border_type, edges));
+        }
 
-        let inner = game_block.inner(game_board_area);
-        f.render_widget(game_block, game_board_area);
+        if config.game_border {
+            f.render_widget(game_block, layout.area);
+        }
+
+        // Flash the edge the snake just came out of after wrapping
+        if let Some(heading) = game.last_wrap().filter(|_| config.game_border) {
+            let area = layout.area;
+            let edge = match heading {
+                Heading::Right => Rect::new(area.x, area.y, 1, area.height),
+                Heading::Left => Rect::new(area.right() - 1, area.y, 1, area.height),
+                Heading::Down => Rect::new(area.x, area.y, area.width, 1),
+                Heading::Up => Rect::new(area.x, area.bottom() - 1, area.width, 1),
+            };
+            let flash_style = if config.enable_colors {
+                Style::default()
+                    .fg(Color::LightCyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().add_modifier(Modifier::BOLD)
+            };
+            f.buffer_mut().set_style(edge, flash_style);
+        }
+
+        if config.show_grid {
+            let grid_style = if config.enable_colors {
+                Style::default().fg(grid_color(config.background_color))
+            } else {
+                Style::default().add_modifier(Modifier::DIM)
+            };
+
+            // Drawn first so entities rendered afterwards cover it
+            for y in 0..board_height {
+                for x in 0..board_width {
+                    let grid_widget = Paragraph::new("·")
+                        .style(grid_style)
+                        .alignment(Alignment::Center);
+                    f.render_widget(grid_widget, layout.cell_rect(Position::new(x, y)));
+                }
+            }
+        }
+
+        // Shade the cells outside a non-rectangular or shrunken board as walls
+        if config.board_shape != BoardShape::Rectangle || game.shrink_inset() > 0 {
+            for y in 0..board_height {
+                for x in 0..board_width {
+                    let pos = Position::new(x, y);
+                    if game.is_playable(pos) {
+                        continue;
+                    }
+                    let wall_widget = Paragraph::new("░")
+                        .style(wall_style)
+                        .alignment(Alignment::Center);
+                    f.render_widget(wall_widget, layout.cell_rect(pos));
+                }
+            }
+        }
+
+        // Soft obstacles are drawn lighter, as the snake can pass them
+        let obstacle_glyph = if game.solid_obstacles() { "▓" } else { "▒" };
+        for &obstacle in game.obstacles() {
+            if obstacle.x >= board_width || obstacle.y >= board_height {
+                continue;
+            }
+            let obstacle_widget = Paragraph::new(obstacle_glyph)
+                .style(wall_style)
+                .alignment(Alignment::Center);
+            f.render_widget(obstacle_widget, layout.cell_rect(obstacle));
+        }
+
+        // Blinking spikes alternate glyphs every few ticks
+        let spike_glyph = if config.spike_blink && (context.tick / 4).is_multiple_of(2) {
+            "✕"
+        } else {
+            "✖"
+        };
+        let spike_style = if config.enable_colors {
+            Style::default().fg(Color::LightMagenta)
+        } else {
+            Style::default().add_modifier(Modifier::BOLD)
+        };
+        for &spike in game.spikes() {
+            if spike.x >= board_width || spike.y >= board_height {
+                continue;
+            }
+            let spike_widget = Paragraph::new(spike_glyph)
+                .style(spike_style)
+                .alignment(Alignment::Center);
+            f.render_widget(spike_widget, layout.cell_rect(spike));
+        }
+
+        // Faint marker where the head goes next, drawn under everything else
+        if config.ghost_preview && game.state() == GameState::Playing {
+            if let Some(next) = game.next_head() {
+                let ghost_style = if config.enable_colors {
+                    Style::default()
+                        .fg(config.snake_color)
+                        .add_modifier(Modifier::DIM)
+                } else {
+                    Style::default().add_modifier(Modifier::DIM)
+                };
+                let ghost_widget = Paragraph::new("•")
+                    .style(ghost_style)
+                    .alignment(Alignment::Center);
+                f.render_widget(ghost_widget, layout.cell_rect(next));
+            }
+        }
 
         // Render snake
         let snake_style = if config.enable_colors {
@@ -106,23 +768,36 @@ impl TuiRenderer {
             Style::default()
         };
 
-        for (i, segment) in game.snake().body().iter().enumerate() {
+        let body = context.replay_snake.unwrap_or(game.snake().body());
+        for (i, segment) in body.iter().enumerate() {
             // Skip if position is out of bounds for the game board
-            if segment.x >= config.board_width || segment.y >= config.board_height {
+            if segment.x >= board_width || segment.y >= board_height {
                 continue;
             }
 
-            let x = inner.x + (segment.x * cell_size * 2);
-            let y = inner.y + (segment.y * cell_size);
-
-            let symbol = if i == 0 { "●" } else { "○" }; // Head vs body
+            // Head vs body
+            let symbol = if i == 0 {
+                "●"
+            } else if config.connected_body {
+                body_glyph(
+                    *segment,
+                    [body.get(i - 1).copied(), body.get(i + 1).copied()],
+                )
+            } else {
+                "○"
+            };
 
             // Create a cell area
-            let segment_area = Rect::new(x, y, cell_size, cell_size);
+            let segment_area = layout.cell_rect(*segment);
 
             if segment_area.width > 0 && segment_area.height > 0 {
+                let segment_style = if config.rainbow && config.enable_colors {
+                    Style::default().fg(rainbow_color(i, context.frame))
+                } else {
+                    snake_style
+                };
                 let segment_widget = Paragraph::new(symbol)
-                    .style(snake_style)
+                    .style(segment_style)
                     .alignment(Alignment::Center);
                 f.render_widget(segment_widget, segment_area);
             }
@@ -135,85 +810,336 @@ impl TuiRenderer {
             Style::default()
         };
 
-        let food = game.food();
+        // Flash the cell where food was just eaten, on top of the head
+        if let Some(eaten) = game.last_eaten().filter(|_| config.eat_flash) {
+            if eaten.x < board_width && eaten.y < board_height {
+                let flash_style = if config.enable_colors {
+                    Style::default()
+                        .fg(Color::LightYellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().add_modifier(Modifier::BOLD)
+                };
+                let flash_widget = Paragraph::new("✸")
+                    .style(flash_style)
+                    .alignment(Alignment::Center);
+                f.render_widget(flash_widget, layout.cell_rect(eaten));
+            }
+        }
+
+        // Render big food by filling every cell it covers
+        if let Some((top_left, size)) = game.big_food() {
+            for dy in 0..size {
+                for dx in 0..size {
+                    let (cell_x, cell_y) = (top_left.x + dx, top_left.y + dy);
+                    if cell_x >= board_width || cell_y >= board_height {
+                        continue;
+                    }
+
+                    let big_food_area = layout.cell_rect(Position::new(cell_x, cell_y));
+                    let big_food_widget = Paragraph::new("◆")
+                        .style(food_style)
+                        .alignment(Alignment::Center);
+                    f.render_widget(big_food_widget, big_food_area);
+                }
+            }
+        }
+
+        // Render power-up
+        if let Some((pos, kind)) = game.power_up() {
+            if pos.x < board_width && pos.y < board_height {
+                let (symbol, color) = match kind {
+                    PowerUpKind::Slow => ("▼", Color::LightBlue),
+                    PowerUpKind::Fast => ("▲", Color::LightYellow),
+                };
+                let power_up_style = if config.enable_colors {
+                    Style::default().fg(color)
+                } else {
+                    Style::default()
+                };
+
+                let power_up_area = layout.cell_rect(pos);
+                let power_up_widget = Paragraph::new(symbol)
+                    .style(power_up_style)
+                    .alignment(Alignment::Center);
+                f.render_widget(power_up_widget, power_up_area);
+            }
+        }
+
+        // Nothing to draw while the food is respawning
+        let Some(food) = game.food() else {
+            return;
+        };
 
         // Skip if food position is out of bounds for the game board
-        if food.x >= config.board_width || food.y >= config.board_height {
+        if food.x >= board_width || food.y >= board_height {
             return;
         }
 
-        let food_x = inner.x + (food.x * cell_size * 2);
-        let food_y = inner.y + (food.y * cell_size);
-
-        let food_area = Rect::new(food_x, food_y, cell_size, cell_size);
+        let food_area = layout.cell_rect(food);
 
         if food_area.width > 0 && food_area.height > 0 {
-            let food_widget = Paragraph::new("◆")
-                .style(food_style)
+            // Alternate with a hollow glyph every `food_pulse_ticks` ticks
+            let hollow = config.food_pulse
+                && (context.tick / u64::from(config.food_pulse_ticks.max(1))) % 2 == 1;
+            let (symbol, style) = match game.food_kind() {
+                FoodKind::Normal => (if hollow { "◇" } else { "◆" }, food_style),
+                FoodKind::Golden if config.enable_colors => (
+                    if hollow { "☆" } else { "★" },
+                    Style::default()
+                        .fg(Color::LightYellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                FoodKind::Golden => (if hollow { "☆" } else { "★" }, food_style),
+            };
+            let food_widget = Paragraph::new(symbol)
+                .style(style)
                 .alignment(Alignment::Center);
             f.render_widget(food_widget, food_area);
         }
     }
 
-    fn render_score_area(&self, f: &mut Frame, game: &Game, config: &GameConfig, area: Rect) {
+    fn score_lines(
+        &self,
+        game: &Game,
+        config: &GameConfig,
+        context: &FrameContext,
+    ) -> Vec<Line<'static>> {
         let border_color = if config.enable_colors {
             config.border_color
         } else {
             Color::White
         };
 
-        let score_text = vec![
-            Line::from(vec![
-                Span::styled("Score: ", Style::default().fg(border_color)),
-                Span::styled(
-                    game.score().to_string(),
-                    Style::default()
-                        .fg(if config.enable_colors {
-                            Color::Yellow
-                        } else {
-                            Color::White
-                        })
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled("High Score: ", Style::default().fg(border_color)),
-                Span::styled(
-                    config.high_score.to_string(),
-                    Style::default()
-                        .fg(if config.enable_colors {
-                            Color::Magenta
-                        } else {
-                            Color::White
-                        })
-                        .add_modifier(Modifier::BOLD),
+        let mut score_text = vec![
+            stat_line(
+                config,
+                &config.messages.score,
+                config.score_format.format(game.score()),
+                Color::Yellow,
+            ),
+            stat_line(
+                config,
+                &format!(
+                    "{} {}x{}",
+                    config.messages.best, config.board_width, config.board_height
                 ),
-            ]),
-            Line::from(vec![
-                Span::styled("Length: ", Style::default().fg(border_color)),
-                Span::styled(
-                    game.snake().len().to_string(),
-                    Style::default()
-                        .fg(if config.enable_colors {
-                            Color::Cyan
-                        } else {
-                            Color::White
-                        })
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
+                config.score_format.format(config.high_score),
+                Color::Magenta,
+            ),
+            stat_line(
+                config,
+                &config.messages.length,
+                game.snake().len().to_string(),
+                Color::Cyan,
+            ),
         ];
 
+        if config.lives > 1 {
+            score_text.push(stat_line(
+                config,
+                &config.messages.lives,
+                "♥".repeat(game.lives() as usize),
+                Color::LightRed,
+            ));
+        }
+
+        if game.combo() > 0 {
+            score_text.push(stat_line(
+                config,
+                &config.messages.combo,
+                format!("x{} ({})", config.combo_multiplier, game.combo()),
+                Color::LightYellow,
+            ));
+        }
+
+        if game.is_wrapping() {
+            score_text.push(stat_line(
+                config,
+                &config.messages.wraps,
+                game.wrap_count().to_string(),
+                Color::Cyan,
+            ));
+        }
+
+        let best_survival = config.best_survival.as_secs();
+        score_text.push(stat_line(
+            config,
+            &config.messages.longest,
+            config.longest_snake.to_string(),
+            Color::Cyan,
+        ));
+        score_text.push(stat_line(
+            config,
+            &config.messages.best_time,
+            format!("{}:{:02}", best_survival / 60, best_survival % 60),
+            Color::Magenta,
+        ));
+
+        let cells_per_second = 1.0 / context.tick_rate.as_secs_f64().max(f64::EPSILON);
+        score_text.push(stat_line(
+            config,
+            &config.messages.speed,
+            format!("{:.1} {}", cells_per_second, config.messages.speed_unit),
+            Color::LightBlue,
+        ));
+
+        // Ten-segment stamina bar
+        let filled = (context.stamina.clamp(0.0, 1.0) * 10.0).round() as usize;
+        score_text.push(Line::from(vec![
+            Span::styled(
+                format!("{}: ", config.messages.stamina),
+                Style::default().fg(border_color),
+            ),
+            Span::styled(
+                format!("{}{}", "█".repeat(filled), "░".repeat(10 - filled)),
+                Style::default().fg(if config.enable_colors {
+                    Color::LightGreen
+                } else {
+                    Color::White
+                }),
+            ),
+        ]));
+
+        // Only while there's still food to chase
+        let head = game.snake().head();
+        let hint = game
+            .food()
+            .filter(|_| {
+                config.food_hint && matches!(game.state(), GameState::Playing | GameState::Paused)
+            })
+            .and_then(|food| Some((food, food_hint_arrow(head, food)?)));
+        if let Some((food, arrow)) = hint {
+            let distance = head.manhattan_distance(food);
+            score_text.push(stat_line(
+                config,
+                &config.messages.food,
+                format!("{} {}", arrow, distance),
+                config.food_color,
+            ));
+        }
+
+        if let Some(date) = config.daily_challenge {
+            score_text.push(Line::from(Span::styled(
+                config.messages.daily_challenge.clone(),
+                Style::default()
+                    .fg(if config.enable_colors {
+                        Color::LightGreen
+                    } else {
+                        Color::White
+                    })
+                    .add_modifier(Modifier::BOLD),
+            )));
+            score_text.push(Line::from(Span::styled(
+                date.to_string(),
+                Style::default().fg(border_color),
+            )));
+        }
+
+        // Blank rows that the combo meter and progress bar are drawn over
+        if shows_combo_meter(game, config) {
+            score_text.push(Line::default());
+        }
+        if config.show_progress {
+            score_text.push(Line::default());
+        }
+
+        score_text
+    }
+
+    fn render_score_area(
+        &self,
+        f: &mut Frame,
+        game: &Game,
+        config: &GameConfig,
+        context: &FrameContext,
+        area: Rect,
+    ) {
+        let border_color = if config.enable_colors {
+            config.border_color
+        } else {
+            Color::White
+        };
+
+        let score_text = self.score_lines(game, config, context);
+        let rows = u16::try_from(score_text.len()).unwrap_or(u16::MAX);
+
         let score_block = Block::default()
-            .title("Stats")
+            .title(config.messages.stats_title.as_str())
             .borders(Borders::ALL)
+            .border_type(config.border_type)
             .border_style(Style::default().fg(border_color));
 
+        let inner = score_block.inner(area);
         let score_paragraph = Paragraph::new(score_text)
             .block(score_block)
             .alignment(Alignment::Left);
 
         f.render_widget(score_paragraph, area);
+
+        // The bars go on the blank rows left for them at the end of the
+        // stats, and are skipped if the panel cuts those rows off
+        let row_from_end = |n: u16| {
+            let row = inner.y + rows.checked_sub(n + 1)?;
+            (row < inner.bottom()).then_some(row)
+        };
+
+        if let Some(row) = row_from_end(0).filter(|_| config.show_progress) {
+            let length = game.snake().len();
+            let goal = game.win_length();
+            let gauge_style = if config.enable_colors {
+                Style::default().fg(config.snake_color)
+            } else {
+                Style::default()
+            };
+            let gauge = Gauge::default()
+                .gauge_style(gauge_style)
+                .ratio(progress_ratio(length, goal))
+                .label(format!("{}/{}", length, goal));
+            f.render_widget(gauge, Rect::new(inner.x, row, inner.width, 1));
+        }
+
+        // Just above the progress bar, if there is one
+        let combo_row = row_from_end(u16::from(config.show_progress));
+        if let Some(row) = combo_row.filter(|_| shows_combo_meter(game, config)) {
+            let gauge_style = if config.enable_colors {
+                Style::default().fg(Color::LightYellow)
+            } else {
+                Style::default()
+            };
+            let gauge = Gauge::default()
+                .gauge_style(gauge_style)
+                .ratio(combo_meter_ratio(game))
+                .label(config.messages.combo.as_str());
+            f.render_widget(gauge, Rect::new(inner.x, row, inner.width, 1));
+        }
+    }
+
+    /// Minimal HUD: score, best and length on a single line, without the
+    /// panels.
+    fn render_status_line(
+        &self,
+        f: &mut Frame,
+        game: &Game,
+        config: &GameConfig,
+        context: &FrameContext,
+        area: Rect,
+    ) {
+        // The first stat lines are always score, best and length
+        let mut spans = Vec::new();
+        for (i, line) in self
+            .score_lines(game, config, context)
+            .into_iter()
+            .take(3)
+            .enumerate()
+        {
+            if i > 0 {
+                spans.push(Span::raw("  "));
+            }
+            spans.extend(line.spans);
+        }
+
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
     }
 
     fn render_controls_area(&self, f: &mut Frame, config: &GameConfig, area: Rect) {
@@ -223,16 +1149,50 @@ impl TuiRenderer {
             Color::White
         };
 
-        let controls_text = vec![
-            Line::from("Arrow Keys: Move"),
-            Line::from("Space: Pause/Resume"),
-            Line::from("R: Restart"),
-            Line::from("Q: Quit"),
+        let messages = &config.messages;
+        let key_map = &config.key_map;
+        let hints = [
+            (key_map.move_hint(), &messages.control_move),
+            (key_map.hint(InputAction::Pause), &messages.control_pause),
+            (key_map.hint(InputAction::Boost), &messages.control_sprint),
+            (
+                key_map.hint(InputAction::Restart),
+                &messages.control_restart,
+            ),
+            (
+                key_map.hint(InputAction::Snapshot),
+                &messages.control_snapshot,
+            ),
+            (
+                key_map.hint(InputAction::ToggleWalls),
+                &messages.control_walls,
+            ),
+            (
+                key_map.hint(InputAction::CycleTheme),
+                &messages.control_theme,
+            ),
+            (key_map.hint(InputAction::Quit), &messages.control_quit),
         ];
 
+        // Unbound actions are left out rather than shown without a key
+        let mut controls_text: Vec<Line> = hints
+            .into_iter()
+            .filter(|(keys, _)| !keys.is_empty())
+            .map(|(keys, label)| Line::from(format!("{}: {}", keys, label)))
+            .collect();
+
+        if config.peaceful {
+            controls_text.push(Line::from(""));
+            controls_text.push(Line::from(Span::styled(
+                messages.peaceful_note.as_str(),
+                Style::default().add_modifier(Modifier::ITALIC),
+            )));
+        }
+
         let controls_block = Block::default()
-            .title("Controls")
+            .title(messages.controls_title.as_str())
             .borders(Borders::ALL)
+            .border_type(config.border_type)
             .border_style(Style::default().fg(border_color));
 
         let controls_paragraph = Paragraph::new(controls_text)
@@ -242,12 +1202,62 @@ impl TuiRenderer {
         f.render_widget(controls_paragraph, area);
     }
 
-    fn render_overlay(&self, f: &mut Frame, game: &Game, config: &GameConfig) {
+    fn render_overlay(
+        &self,
+        f: &mut Frame,
+        game: &Game,
+        config: &GameConfig,
+        context: &FrameContext,
+    ) {
         let area = f.area();
 
         match game.state() {
             GameState::Paused => {
-                let popup_area = self.centered_rect(30, 20, area);
+                // Let the hint breathe so the popup doesn't look frozen
+                let hint_style = if context.frame.is_multiple_of(2) {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().add_modifier(Modifier::DIM)
+                };
+                let mut pause_text = vec![Line::styled(
+                    fill_key(
+                        &config.messages.resume_hint,
+                        &config.key_map.hint(InputAction::Pause),
+                    ),
+                    hint_style,
+                )];
+                if config.pause_stats {
+                    let played = context.time_survived.as_secs();
+                    pause_text.extend([
+                        Line::from(""),
+                        Line::from(format!(
+                            "{}: {}",
+                            config.messages.score,
+                            config.score_format.format(game.score())
+                        )),
+                        Line::from(format!(
+                            "{}: {}",
+                            config.messages.length,
+                            game.snake().len()
+                        )),
+                        Line::from(format!(
+                            "{}: {}:{:02}",
+                            config.messages.time,
+                            played / 60,
+                            played % 60
+                        )),
+                    ]);
+                }
+
+                // Size the popup to its contents, plus borders and a margin
+                let content_width = pause_text
+                    .iter()
+                    .map(Line::width)
+                    .chain([config.messages.paused.chars().count()])
+                    .max()
+                    .unwrap_or(0) as u16;
+                let popup_area =
+                    self.fitted_rect(content_width + 4, pause_text.len() as u16 + 2, area);
                 f.render_widget(Clear, popup_area);
 
                 let border_color = if config.enable_colors {
@@ -257,15 +1267,15 @@ impl TuiRenderer {
                 };
 
                 let pause_block = Block::default()
-                    .title("PAUSED")
+                    .title(config.messages.paused.as_str())
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(border_color));
 
-                let pause_text = Paragraph::new("Press Space to resume")
+                let pause_paragraph = Paragraph::new(pause_text)
                     .block(pause_block)
                     .alignment(Alignment::Center);
 
-                f.render_widget(pause_text, popup_area);
+                f.render_widget(pause_paragraph, popup_area);
             }
             GameState::GameOver => {
                 let popup_area = self.centered_rect(40, 30, area);
@@ -278,16 +1288,19 @@ impl TuiRenderer {
                 };
 
                 let game_over_block = Block::default()
-                    .title("GAME OVER")
+                    .title(config.messages.game_over.as_str())
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(border_color));
 
                 let game_over_text = vec![
                     Line::from(""),
                     Line::from(vec![
-                        Span::styled("Final Score: ", Style::default()),
                         Span::styled(
-                            game.score().to_string(),
+                            format!("{}: ", config.messages.final_score),
+                            Style::default(),
+                        ),
+                        Span::styled(
+                            config.score_format.format(game.score()),
                             Style::default()
                                 .fg(if config.enable_colors {
                                     Color::Yellow
@@ -298,8 +1311,8 @@ impl TuiRenderer {
                         ),
                     ]),
                     Line::from(""),
-                    Line::from("Press R to restart"),
-                    Line::from("Press Q to quit"),
+                    Line::from(restart_hint(config)),
+                    Line::from(quit_hint(config)),
                 ];
 
                 let game_over_paragraph = Paragraph::new(game_over_text)
@@ -308,10 +1321,107 @@ impl TuiRenderer {
 
                 f.render_widget(game_over_paragraph, popup_area);
             }
+            GameState::Won => self.render_win_overlay(f, game, config, context.win_frame),
             GameState::Playing => {}
         }
     }
 
+    fn render_win_overlay(&self, f: &mut Frame, game: &Game, config: &GameConfig, frame: u32) {
+        let popup_area = self.centered_rect(40, 30, f.area());
+        f.render_widget(Clear, popup_area);
+
+        // Cycle the border through bright colors for a short celebration
+        let border_color = if !config.enable_colors {
+            Color::White
+        } else if frame < WIN_ANIMATION_FRAMES {
+            WIN_COLORS[frame as usize % WIN_COLORS.len()]
+        } else {
+            Color::Yellow
+        };
+
+        let win_block = Block::default()
+            .title(config.messages.you_win.as_str())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(Style::default().fg(border_color));
+
+        let win_text = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                config.messages.you_win.as_str(),
+                Style::default()
+                    .fg(border_color)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(vec![
+                Span::styled(
+                    format!("{}: ", config.messages.final_score),
+                    Style::default(),
+                ),
+                Span::styled(
+                    config.score_format.format(game.score()),
+                    Style::default()
+                        .fg(if config.enable_colors {
+                            Color::Yellow
+                        } else {
+                            Color::White
+                        })
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(restart_hint(config)),
+            Line::from(quit_hint(config)),
+        ];
+
+        let win_paragraph = Paragraph::new(win_text)
+            .block(win_block)
+            .alignment(Alignment::Center);
+
+        f.render_widget(win_paragraph, popup_area);
+    }
+
+    fn render_toast(&self, f: &mut Frame, message: &str, config: &GameConfig) {
+        let area = f.area();
+        let width = (message.chars().count() as u16 + 4).min(area.width);
+        let toast_area =
+            Rect::new(area.x + (area.width - width) / 2, area.y + 1, width, 3).intersection(area);
+        f.render_widget(Clear, toast_area);
+
+        let toast_color = if config.enable_colors {
+            Color::Yellow
+        } else {
+            Color::White
+        };
+
+        let toast_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(toast_color));
+
+        let toast_paragraph = Paragraph::new(message)
+            .style(
+                Style::default()
+                    .fg(toast_color)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .block(toast_block)
+            .alignment(Alignment::Center);
+
+        f.render_widget(toast_paragraph, toast_area);
+    }
+
+    /// A `width` by `height` rect centered in `r`, shrunk to fit if needed.
+    fn fitted_rect(&self, width: u16, height: u16, r: Rect) -> Rect {
+        let width = width.min(r.width);
+        let height = height.min(r.height);
+        Rect::new(
+            r.x + (r.width - width) / 2,
+            r.y + (r.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
     fn centered_rect(&self, percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         let popup_layout = Layout::default()
             .direction(Direction::Vertical)
@@ -336,6 +1446,10 @@ impl TuiRenderer {
 impl Renderer for TuiRenderer {
     type Error = std::io::Error;
 
+    fn draw(&mut self, f: &mut Frame, game: &Game, config: &GameConfig, context: &FrameContext) {
+        self.draw_frame(f, game, config, context);
+    }
+
     fn render(&mut self, _game: &Game, _config: &GameConfig) -> Result<(), Self::Error> {
         // This will be called from the main application loop
         // The actual frame rendering is handled by the main loop
@@ -349,26 +1463,785 @@ impl Renderer for TuiRenderer {
 
 // Helper function for the main application to use
 impl TuiRenderer {
-    pub fn draw_frame(&self, f: &mut Frame, game: &Game, config: &GameConfig) {
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Min(50),    // Game area
-                Constraint::Length(25), // Side panel
-            ])
-            .split(f.area());
+    pub fn draw_frame(
+        &self,
+        f: &mut Frame,
+        game: &Game,
+        config: &GameConfig,
+        context: &FrameContext,
+    ) {
+        let chunks = main_chunks(config, f.area());
 
-        let side_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(6), // Score (increased for high score)
-                Constraint::Min(8),    // Controls
-            ])
-            .split(chunks[1]);
+        self.render_game_area(f, game, config, context, chunks[0]);
+        if config.pause_dim && game.state() == GameState::Paused {
+            // Push the board into the background behind the pause popup
+            f.buffer_mut()
+                .set_style(chunks[0], Style::default().add_modifier(Modifier::DIM));
+        }
+
+        if config.hud_minimal {
+            self.render_status_line(f, game, config, context, chunks[1]);
+        } else {
+            // Score panel grows with the number of stat lines, +2 for border
+            let score_height = self.score_lines(game, config, context).len() as u16 + 2;
+
+            let side_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(score_height), // Score
+                    Constraint::Min(8),               // Controls
+                ])
+                .split(chunks[1]);
+
+            self.render_score_area(f, game, config, context, side_chunks[0]);
+            self.render_controls_area(f, config, side_chunks[1]);
+        }
+        // Hold the popup back until the replay has shown the crash
+        if context.replay_snake.is_none() {
+            self.render_overlay(f, game, config, context);
+        }
+
+        if let Some(message) = context.toast {
+            self.render_toast(f, message, config);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Anchor;
+    use crate::input::KeyMap;
+    use crate::messages::Messages;
+    use crate::scenario::Scenario;
+    use crossterm::event::KeyCode;
+    use ratatui::{Terminal, backend::TestBackend, buffer::Buffer};
+
+    const TEST_AREA: Rect = Rect::new(0, 0, 100, 30);
+
+    fn render_to_buffer(game: &Game, config: &GameConfig, context: &FrameContext) -> Buffer {
+        render_sized(game, config, context, TEST_AREA.width, TEST_AREA.height)
+    }
+
+    /// Draws one frame off-screen, exactly as `draw_frame` would on a
+    /// terminal of `width` x `height` cells.
+    fn render_sized(
+        game: &Game,
+        config: &GameConfig,
+        context: &FrameContext,
+        width: u16,
+        height: u16,
+    ) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|f| TuiRenderer::new().draw_frame(f, game, config, context))
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn render_to_string(game: &Game, config: &GameConfig, context: &FrameContext) -> String {
+        buffer_text(&render_to_buffer(game, config, context))
+    }
+
+    fn buffer_text(buffer: &Buffer) -> String {
+        let mut text = String::new();
+        for y in 0..buffer.area.height {
+            for x in 0..buffer.area.width {
+                text.push_str(buffer[(x, y)].symbol());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Symbol drawn at the top-left of the board cell `pos`.
+    fn cell_symbol(buffer: &Buffer, config: &GameConfig, pos: Position) -> String {
+        let dimensions = (config.board_width, config.board_height);
+        let layout = BoardLayout::for_frame(config, dimensions, TEST_AREA).unwrap();
+        let cell = layout.cell_rect(pos);
+        buffer[(cell.x, cell.y)].symbol().to_string()
+    }
+
+    #[test]
+    fn test_speed_line_reflects_tick_rate() {
+        let config = GameConfig::new(20, 15);
+        let game = Game::new(20, 15);
+        let context = FrameContext {
+            tick_rate: Duration::from_millis(200),
+            ..Default::default()
+        };
+
+        let text = render_to_string(&game, &config, &context);
+
+        assert!(text.contains("Speed: 5.0 cells/s"));
+    }
+
+    #[test]
+    fn test_custom_title_is_rendered() {
+        let config = GameConfig::new(20, 15).with_title(String::from("Serpent Deluxe"));
+        let game = Game::new(20, 15);
+
+        let text = render_to_string(&game, &config, &FrameContext::default());
+
+        assert!(text.contains("Serpent Deluxe"));
+        assert!(!text.contains("Snake Game"));
+    }
+
+    #[test]
+    fn test_title_shows_paused_suffix() {
+        let config = GameConfig::new(20, 15).with_title(String::from("Serpent Deluxe"));
+        let mut game = Game::new(20, 15);
+
+        let title_row = |game: &Game| {
+            render_to_string(game, &config, &FrameContext::default())
+                .lines()
+                .next()
+                .unwrap()
+                .to_string()
+        };
+        assert!(!title_row(&game).contains("[PAUSED]"));
+
+        game.toggle_pause();
+        assert!(title_row(&game).contains("Serpent Deluxe [PAUSED]"));
+    }
+
+    #[test]
+    fn test_pause_popup_shows_stats_when_enabled() {
+        let mut game = Game::new(20, 15);
+        game.toggle_pause();
+        let context = FrameContext {
+            time_survived: Duration::from_secs(75),
+            ..FrameContext::default()
+        };
+
+        let plain = render_to_string(&game, &GameConfig::new(20, 15), &context);
+        assert!(!plain.contains("Time: 1:15"));
+
+        let config = GameConfig::new(20, 15).with_pause_stats(true);
+        let text = render_to_string(&game, &config, &context);
+        let popup_row = |needle: &str| {
+            text.lines()
+                .find(|l| l.contains(needle))
+                .unwrap()
+                .to_string()
+        };
+        assert!(popup_row("Length: 5").contains("│"));
+        assert!(text.contains("Score: 0 "));
+        assert!(text.contains("Time: 1:15"));
+
+        // The popup stays centered on the hint line
+        let hint = "Press Space to resume";
+        let row = popup_row(hint);
+        let start = row[..row.find(hint).unwrap()].chars().count();
+        let end = start + hint.len();
+        let width = row.chars().count();
+        assert!(
+            start.abs_diff(width - end) <= 1,
+            "{start} vs {}",
+            width - end
+        );
+    }
+
+    #[test]
+    fn test_minimal_hud_is_a_single_status_line() {
+        let config = GameConfig::new(20, 15).with_hud_minimal(true);
+        let game = Game::new(20, 15);
+
+        let text = render_to_string(&game, &config, &FrameContext::default());
+        let status = text.lines().last().unwrap();
+
+        assert!(status.contains("Score: 0"));
+        assert!(status.contains("Length: 5"));
+        assert!(!text.contains("Controls"));
+        assert!(!text.contains("Stats"));
+    }
+
+    #[test]
+    fn test_timed_food_shifts_from_green_to_red() {
+        let config = GameConfig::new(20, 15);
+
+        assert_eq!(
+            timed_food_style(&config, 40, 40).fg,
+            Some(Color::Rgb(0, 255, 0))
+        );
+        assert_eq!(
+            timed_food_style(&config, 20, 40).fg,
+            Some(Color::Rgb(255, 255, 0))
+        );
+        assert_eq!(
+            timed_food_style(&config, 1, 40).fg,
+            Some(Color::Rgb(255, 13, 0))
+        );
+
+        let plain = GameConfig::new(20, 15).with_colors(false);
+        assert_eq!(timed_food_style(&plain, 1, 40), Style::default());
+    }
+
+    #[test]
+    fn test_rainbow_segment_color_changes_with_frame() {
+        let config = GameConfig::new(20, 15).with_rainbow(true);
+        let game = Game::from_config(&config);
+        let layout = BoardLayout::for_frame(&config, game.dimensions(), TEST_AREA).unwrap();
+        let cell = layout.cell_rect(game.snake().body()[2]);
+
+        let color_at = |frame| {
+            let context = FrameContext {
+                frame,
+                ..Default::default()
+            };
+            render_to_buffer(&game, &config, &context)[(cell.x, cell.y)].fg
+        };
+        assert_eq!(color_at(0), rainbow_color(2, 0));
+        assert_ne!(color_at(0), color_at(1));
+
+        let plain = config.with_colors(false);
+        let buffer = render_to_buffer(&game, &plain, &FrameContext::default());
+        assert_eq!(buffer[(cell.x, cell.y)].fg, Color::Reset);
+    }
+
+    #[test]
+    fn test_rounded_border_type_is_rendered() {
+        let game = Game::new(20, 15);
+
+        let plain = render_to_string(&game, &GameConfig::new(20, 15), &FrameContext::default());
+        assert!(!plain.contains('╭'));
+
+        let config = GameConfig::new(20, 15).with_border_type(BorderType::Rounded);
+        let rounded = render_to_string(&game, &config, &FrameContext::default());
+        assert!(rounded.contains('╭'));
+        assert!(rounded.contains('╯'));
+    }
+
+    #[test]
+    fn test_board_border_is_dashed_only_while_wrapping() {
+        let top_edge = |config: &GameConfig, game: &Game| {
+            let buffer = render_to_buffer(game, config, &FrameContext::default());
+            let layout = BoardLayout::for_frame(config, game.dimensions(), TEST_AREA).unwrap();
+            buffer[(layout.area.x + 1, layout.area.y)]
+                .symbol()
+                .to_string()
+        };
+
+        let config = GameConfig::new(20, 15).with_wall_wrapping(true);
+        let mut game = Game::from_config(&config);
+        assert_eq!(top_edge(&config, &game), "╌");
+
+        game.set_wall_wrapping(false);
+        assert_eq!(top_edge(&config, &game), "─");
+    }
+
+    #[test]
+    fn test_grid_drawn_in_empty_cells_only_when_enabled() {
+        let game = Game::new(20, 15);
+        let head = game.snake().head();
+        let empty = (0..20)
+            .map(|x| Position::new(x, 14))
+            .find(|&pos| game.food() != Some(pos) && !game.snake().body().contains(&pos))
+            .unwrap();
+
+        let config = GameConfig::new(20, 15).with_grid(true);
+        let buffer = render_to_buffer(&game, &config, &FrameContext::default());
+        assert_eq!(cell_symbol(&buffer, &config, empty), "·");
+        assert_eq!(cell_symbol(&buffer, &config, head), "●");
+
+        let config = GameConfig::new(20, 15);
+        let buffer = render_to_buffer(&game, &config, &FrameContext::default());
+        assert_eq!(cell_symbol(&buffer, &config, empty), " ");
+    }
+
+    #[test]
+    fn test_combo_meter_drains_with_ticks_since_food() {
+        let scenario = Scenario {
+            board_width: 20,
+            board_height: 15,
+            body: vec![
+                Position::new(5, 7),
+                Position::new(4, 7),
+                Position::new(3, 7),
+            ],
+            direction: Heading::Right,
+            food: Some(Position::new(6, 7)),
+            score: 0,
+        };
+        let config = GameConfig::new(20, 15)
+            .with_scenario(scenario)
+            .with_seed(4)
+            .with_combo(10, 2)
+            .with_combo_meter(true);
+        let mut game = Game::from_config(&config);
+        assert_eq!(combo_meter_ratio(&game), 0.0);
+
+        game.update();
+        assert_eq!(game.food_eaten(), 1);
+        assert_eq!(combo_meter_ratio(&game), 1.0);
+        for _ in 0..4 {
+            game.update();
+        }
+        assert_eq!(game.ticks_since_food(), 4);
+        assert_eq!(combo_meter_ratio(&game), 0.6);
+
+        // The meter is drawn 60% full across the stats panel; filled cells
+        // under the label are blank with the gauge color as background
+        let buffer = render_to_buffer(&game, &config, &FrameContext::default());
+        let panel = main_chunks(&config, TEST_AREA)[1];
+        let inner_width = panel.width - 2;
+        let row = (0..buffer.area.height)
+            .find(|&y| (panel.x..panel.right()).any(|x| buffer[(x, y)].bg == Color::LightYellow))
+            .expect("the combo meter is drawn");
+        let filled = (panel.x..panel.right())
+            .filter(|&x| {
+                let cell = &buffer[(x, row)];
+                cell.symbol() == ratatui::symbols::block::FULL || cell.bg == Color::LightYellow
+            })
+            .count();
+        assert_eq!(filled, (f64::from(inner_width) * 0.6).round() as usize);
+
+        // It sits on its own row after the stats, above the progress bar
+        let text = buffer_text(&buffer);
+        let stamina = text.lines().position(|line| line.contains("Stamina:"));
+        assert_eq!(Some(usize::from(row)), stamina.map(|row| row + 1));
+        let config = config.with_progress_bar(true);
+        let text = render_to_string(&game, &config, &FrameContext::default());
+        let row_of = |needle: &str| text.lines().position(|line| line.contains(needle));
+        assert_eq!(row_of("Combo"), stamina.map(|row| row + 1));
+        assert_eq!(row_of("4/300"), stamina.map(|row| row + 2));
+    }
+
+    #[test]
+    fn test_win_overlay_shows_banner_and_score() {
+        // One food away from filling the board
+        let scenario = Scenario {
+            board_width: 4,
+            board_height: 1,
+            body: vec![
+                Position::new(2, 0),
+                Position::new(1, 0),
+                Position::new(0, 0),
+            ],
+            direction: Heading::Right,
+            food: Some(Position::new(3, 0)),
+            score: 0,
+        };
+        let config = GameConfig::new(4, 1).with_scenario(scenario);
+        let mut game = Game::from_config(&config);
+        game.update();
+        assert_eq!(game.state(), GameState::Won);
+
+        let context = FrameContext {
+            win_frame: 3,
+            ..FrameContext::default()
+        };
+        let text: String = render_to_buffer(&game, &config, &context)
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert!(text.contains("YOU WIN!"));
+        assert!(text.contains(&format!("Final Score: {}", game.score())));
+        assert!(text.contains("Press R to restart"));
+    }
+
+    #[test]
+    fn test_overlay_hints_name_rebound_keys() {
+        let key_map = KeyMap::default()
+            .bind(InputAction::Pause, &[KeyCode::Char('p')])
+            .bind(InputAction::Restart, &[KeyCode::F(5)]);
+        let config = GameConfig::new(20, 15).with_key_map(key_map);
+        let mut game = Game::new(20, 15);
+        let text = |game: &Game| -> String {
+            render_to_buffer(game, &config, &FrameContext::default())
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect()
+        };
+
+        game.toggle_pause();
+        assert!(text(&game).contains("Press P to resume"));
+
+        game.toggle_pause();
+        while game.state() == GameState::Playing {
+            game.update();
+        }
+        let text = text(&game);
+        assert!(text.contains("Press F5 to restart"));
+        assert!(text.contains("Press Q/Esc to quit"));
+    }
+
+    #[test]
+    fn test_pause_overlay_uses_message_table() {
+        let messages = Messages {
+            paused: String::from("EN PAUSE"),
+            ..Messages::default()
+        };
+        let config = GameConfig::new(20, 15).with_messages(messages);
+        let mut game = Game::new(20, 15);
+        game.toggle_pause();
+
+        let text = render_to_string(&game, &config, &FrameContext::default());
+
+        assert!(text.contains("EN PAUSE"));
+        assert!(!text.contains("PAUSED"));
+    }
+
+    #[test]
+    fn test_controls_panel_shows_custom_bindings() {
+        let key_map = KeyMap::default().bind(InputAction::Pause, &[KeyCode::Char('p')]);
+        let config = GameConfig::new(20, 15).with_key_map(key_map);
+        let game = Game::new(20, 15);
+
+        let text = render_to_string(&game, &config, &FrameContext::default());
+
+        assert!(text.contains("P: Pause/Resume"));
+        assert!(!text.contains("Space: Pause/Resume"));
+        assert!(text.contains("Q/Esc: Quit"));
+    }
+
+    #[test]
+    fn test_food_hint_arrow_points_towards_food() {
+        let head = Position::new(10, 10);
+
+        assert_eq!(food_hint_arrow(head, Position::new(4, 2)), Some("↖"));
+        assert_eq!(food_hint_arrow(head, Position::new(10, 12)), Some("↓"));
+        assert_eq!(food_hint_arrow(head, head), None);
+    }
+
+    #[test]
+    fn test_progress_bar_shows_length_towards_full_board() {
+        let config = GameConfig::new(10, 10).with_progress_bar(true);
+        let game = Game::from_config(&config);
+
+        assert_eq!(progress_ratio(game.snake().len(), game.win_length()), 0.05);
+        assert_eq!(progress_ratio(150, 100), 1.0);
+        let text = render_to_string(&game, &config, &FrameContext::default());
+        assert!(text.contains("5/100"));
+
+        // Right below the last stat rather than at the bottom of the panel
+        let row_of = |needle: &str| text.lines().position(|line| line.contains(needle));
+        assert_eq!(row_of("5/100"), row_of("Stamina:").map(|row| row + 1));
+    }
+
+    #[test]
+    fn test_body_glyph_connects_neighbors() {
+        let segment = Position::new(5, 5);
+
+        let straight = [Some(Position::new(4, 5)), Some(Position::new(6, 5))];
+        assert_eq!(body_glyph(segment, straight), "─");
+
+        let corner = [Some(Position::new(5, 4)), Some(Position::new(6, 5))];
+        assert_eq!(body_glyph(segment, corner), "└");
+
+        // No connector across a wrapped edge
+        let wrapped = [Some(Position::new(0, 5)), None];
+        assert_eq!(body_glyph(Position::new(9, 5), wrapped), "○");
+    }
+
+    #[test]
+    fn test_top_left_anchor_pins_board_to_corner() {
+        let config = GameConfig::new(10, 5).with_board_anchor(Anchor::TopLeft);
+        let game = Game::new(10, 5);
+        let game_area = main_chunks(&config, TEST_AREA)[0];
+        let outer_inner = Block::default().borders(Borders::ALL).inner(game_area);
+
+        let layout = BoardLayout::for_frame(&config, game.dimensions(), TEST_AREA).unwrap();
+        assert_eq!(
+            (layout.area.x, layout.area.y),
+            (outer_inner.x, outer_inner.y)
+        );
+
+        let buffer = render_to_buffer(&game, &config, &FrameContext::default());
+        let corner = config.border_type.to_border_set().top_left;
+        assert_eq!(buffer[(outer_inner.x, outer_inner.y)].symbol(), corner);
+
+        // The default still centers the board
+        let centered =
+            BoardLayout::for_frame(&GameConfig::new(10, 5), game.dimensions(), TEST_AREA).unwrap();
+        assert!(centered.area.x > outer_inner.x && centered.area.y > outer_inner.y);
+    }
+
+    #[test]
+    fn test_borderless_board_uses_whole_area() {
+        let available = Rect::new(3, 2, 20, 10);
+        let bordered = GameConfig::new(10, 10);
+        let borderless = GameConfig::new(10, 10).with_game_border(false);
+
+        // The border's two rows don't fit, but the board alone does
+        assert_eq!(BoardLayout::compute(&bordered, (10, 10), available), None);
+        let layout = BoardLayout::compute(&borderless, (10, 10), available).unwrap();
+        assert_eq!(layout.inner, available);
+        assert_eq!(layout.area, available);
+
+        // With room for both, dropping the border adds two columns and rows
+        let roomy = Rect::new(0, 0, 50, 25);
+        let with = BoardLayout::compute(&bordered, (10, 10), roomy).unwrap();
+        let without = BoardLayout::compute(&borderless, (10, 10), roomy).unwrap();
+        assert_eq!(without.area.width, with.inner.width);
+        assert_eq!(with.area.width - without.area.width, 2);
+        assert_eq!(with.area.height - without.area.height, 2);
+
+        let borderless = GameConfig::new(20, 15).with_game_border(false);
+        let game = Game::new(20, 15);
+        let buffer = render_to_buffer(&game, &borderless, &FrameContext::default());
+        let layout = BoardLayout::for_frame(&borderless, game.dimensions(), TEST_AREA).unwrap();
+        let corner = borderless.border_type.to_border_set().top_left;
+        assert_ne!(buffer[(layout.area.x, layout.area.y)].symbol(), corner);
+        assert_eq!(cell_symbol(&buffer, &borderless, game.snake().head()), "●");
+    }
+
+    #[test]
+    fn test_cell_size_respects_configured_limits() {
+        let available = Rect::new(0, 0, 200, 50);
+        let config = GameConfig::new(10, 10);
+
+        let fitted = BoardLayout::compute(&config, (10, 10), available).unwrap();
+        assert_eq!(fitted.cell_size, 4);
+
+        let capped =
+            BoardLayout::compute(&config.clone().with_max_cell_size(2), (10, 10), available)
+                .unwrap();
+        assert_eq!(capped.cell_size, 2);
+        assert_eq!(capped.area.height, 10 * 2 + 2);
+
+        // A minimum that fits zooms in past the maximum
+        let zoomed = config.clone().with_max_cell_size(2).with_min_cell_size(3);
+        let zoomed = BoardLayout::compute(&zoomed, (10, 10), available).unwrap();
+        assert_eq!(zoomed.cell_size, 3);
+
+        // One that doesn't fit leaves the board to the minimap
+        let oversized = config.with_min_cell_size(8);
+        assert_eq!(BoardLayout::compute(&oversized, (10, 10), available), None);
+        let config = GameConfig::new(20, 15).with_min_cell_size(8);
+        let text = render_to_string(&Game::new(20, 15), &config, &FrameContext::default());
+        assert_eq!(text.matches('●').count(), 1);
+        assert!(!text.contains('○'));
+    }
+
+    #[test]
+    fn test_board_layout_stays_inside_available_area() {
+        let available = Rect::new(1, 1, 40, 20);
+        for border in [true, false] {
+            let config = GameConfig::new(10, 10).with_game_border(border);
+            let layout = BoardLayout::compute(&config, (10, 10), available).unwrap();
+            assert_eq!(layout.area.intersection(available), layout.area);
+            assert_eq!(layout.cell_size, if border { 1 } else { 2 });
+        }
+    }
+
+    #[test]
+    fn test_ghost_preview_marks_next_head_cell() {
+        let config = GameConfig::new(20, 15).with_ghost_preview(true);
+        let game = Game::from_config(&config.clone().with_seed(8));
+        let next = game.next_head().unwrap();
+        let head = game.snake().head();
+        assert_eq!(next, Position::new(head.x + 1, head.y));
+
+        let buffer = render_to_buffer(&game, &config, &FrameContext::default());
+        assert_ne!(game.food(), Some(next));
+        assert_eq!(cell_symbol(&buffer, &config, next), "•");
+
+        let plain = render_to_buffer(&game, &GameConfig::new(20, 15), &FrameContext::default());
+        assert_ne!(cell_symbol(&plain, &config, next), "•");
+    }
+
+    #[test]
+    fn test_entry_edge_flashes_after_wrap() {
+        let config = GameConfig::new(10, 10).with_wall_wrapping(true);
+        let mut game = Game::from_config(&config);
+        // The head starts against the right edge
+        game.update();
+        assert_eq!(game.last_wrap(), Some(Heading::Right));
+
+        let buffer = render_to_buffer(&game, &config, &FrameContext::default());
+        let layout = BoardLayout::for_frame(&config, game.dimensions(), TEST_AREA).unwrap();
+        let middle = layout.area.y + layout.area.height / 2;
+
+        assert_eq!(buffer[(layout.area.x, middle)].fg, Color::LightCyan);
+        assert_ne!(
+            buffer[(layout.area.right() - 1, middle)].fg,
+            Color::LightCyan
+        );
+    }
+
+    #[test]
+    fn test_pause_hint_alternates_with_frame() {
+        let config = GameConfig::new(20, 15);
+        let mut game = Game::new(20, 15);
+        game.toggle_pause();
+
+        let hint_modifier = |frame| {
+            let context = FrameContext {
+                frame,
+                ..Default::default()
+            };
+            let buffer = render_to_buffer(&game, &config, &context);
+            let text = buffer_text(&buffer);
+            let row = text
+                .lines()
+                .position(|line| line.contains("Press Space"))
+                .unwrap();
+            let line: Vec<&str> = (0..buffer.area.width)
+                .map(|x| buffer[(x, row as u16)].symbol())
+                .collect();
+            let column = line.windows(5).position(|w| w.concat() == "Press").unwrap();
+            buffer[(column as u16, row as u16)].modifier
+        };
+
+        assert!(hint_modifier(0).contains(Modifier::BOLD));
+        assert!(hint_modifier(1).contains(Modifier::DIM));
+        assert!(hint_modifier(2).contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_board_dimmed_while_paused() {
+        // Tall board so the snake starts above the pause popup
+        let config = GameConfig::new(10, 24);
+        let mut game = Game::new(10, 24);
+        let head = game.snake().head();
+        let layout = BoardLayout::for_frame(&config, game.dimensions(), TEST_AREA).unwrap();
+        let head_cell = layout.cell_rect(head);
+        let is_dim = |buffer: &Buffer| {
+            buffer[(head_cell.x, head_cell.y)]
+                .modifier
+                .contains(Modifier::DIM)
+        };
+
+        let playing = render_to_buffer(&game, &config, &FrameContext::default());
+        assert!(!is_dim(&playing));
+
+        game.toggle_pause();
+        let paused = render_to_buffer(&game, &config, &FrameContext::default());
+        assert_eq!(cell_symbol(&paused, &config, head), "●");
+        assert!(is_dim(&paused));
+
+        let undimmed = render_to_buffer(
+            &game,
+            &config.with_pause_dim(false),
+            &FrameContext::default(),
+        );
+        assert!(!is_dim(&undimmed));
+    }
+
+    #[test]
+    fn test_food_pulses_between_two_glyphs() {
+        let config = GameConfig::new(20, 15)
+            .with_golden_food_chance(0.0)
+            .with_food_pulse(true)
+            .with_food_pulse_interval(3);
+        let game = Game::from_config(&config.clone().with_seed(5));
+        let food_at = |tick| {
+            let context = FrameContext {
+                tick,
+                ..Default::default()
+            };
+            cell_symbol(
+                &render_to_buffer(&game, &config, &context),
+                &config,
+                game.food().unwrap(),
+            )
+        };
+
+        assert_eq!(food_at(0), "◆");
+        assert_eq!(food_at(2), "◆");
+        assert_eq!(food_at(3), "◇");
+        assert_eq!(food_at(6), "◆");
+    }
+
+    #[test]
+    fn test_oversized_board_falls_back_to_minimap() {
+        let config = GameConfig::new(200, 100);
+        let game = Game::from_config(&config);
+        assert!(BoardLayout::for_frame(&config, game.dimensions(), TEST_AREA).is_none());
+
+        let buffer = render_to_buffer(&game, &config, &FrameContext::default());
+        let text = buffer_text(&buffer);
+
+        let outer_inner = Block::default()
+            .borders(Borders::ALL)
+            .inner(main_chunks(&config, TEST_AREA)[0]);
+        let minimap = MinimapLayout::compute(&config, game.dimensions(), outer_inner).unwrap();
+        let (x, y) = minimap.cell_position(game.snake().head());
+        assert_eq!(buffer[(x, y)].symbol(), "●");
+        assert!(!text.contains("Terminal too small!"));
+    }
+
+    #[test]
+    fn test_minimap_follows_board_anchor() {
+        let available = Rect::new(1, 1, 40, 20);
+        let config = GameConfig::new(200, 100);
+
+        let centered = MinimapLayout::compute(&config, (200, 100), available).unwrap();
+        assert!(centered.area.x > available.x || centered.area.y > available.y);
+
+        let config = config.with_board_anchor(Anchor::BottomRight);
+        let anchored = MinimapLayout::compute(&config, (200, 100), available).unwrap();
+        assert_eq!(anchored.area.right(), available.right());
+        assert_eq!(anchored.area.bottom(), available.bottom());
+    }
+
+    #[test]
+    fn test_tiny_terminal_shows_size_warning() {
+        // Two rows inside the outer block can't even hold the minimap border
+        let config = GameConfig::new(30, 20);
+        let game = Game::new(30, 20);
+
+        let buffer = render_sized(&game, &config, &FrameContext::default(), 80, 4);
+        let text = buffer_text(&buffer);
+
+        assert!(text.contains("Terminal too small!"));
+        assert!(text.contains("Minimum size: 62x22"));
+
+        let config = config.with_game_border(false);
+        let buffer = render_sized(&game, &config, &FrameContext::default(), 80, 4);
+        assert!(buffer_text(&buffer).contains("Minimum size: 60x20"));
+    }
+
+    #[test]
+    fn test_minimap_flag_scales_down_a_board_that_fits() {
+        let config = GameConfig::new(20, 15).with_minimap(true);
+        let game = Game::from_config(&config);
+
+        let text = render_to_string(&game, &config, &FrameContext::default());
+
+        assert_eq!(text.matches('●').count(), 1);
+        assert!(!text.contains('○'));
+    }
+
+    #[test]
+    fn test_head_drawn_at_its_board_cell() {
+        let config = GameConfig::new(20, 15);
+        let game = Game::from_config(&config);
+
+        let buffer = render_to_buffer(&game, &config, &FrameContext::default());
+
+        assert_eq!(cell_symbol(&buffer, &config, game.snake().head()), "●");
+    }
+
+    #[test]
+    fn test_fit_board_to_terminal_stays_within_bounds() {
+        let (width, height) = fit_board_to_terminal(&GameConfig::new(200, 200), 100, 30);
+        assert_eq!((width, height), (35, 26));
+
+        // The fitted board must pass the renderer's own size check
+        let config = GameConfig::new(width, height);
+        let game = Game::new(width, height);
+        let text = render_to_string(&game, &config, &FrameContext::default());
+        assert!(!text.contains("Terminal too small!"));
+
+        // Boards that already fit are left alone
+        assert_eq!(
+            fit_board_to_terminal(&GameConfig::new(20, 15), 100, 30),
+            (20, 15)
+        );
 
-        self.render_game_area(f, game, config, chunks[0]);
-        self.render_score_area(f, game, config, side_chunks[0]);
-        self.render_controls_area(f, config, side_chunks[1]);
-        self.render_overlay(f, game, config);
+        // Without the game border or side panel there's room for more
+        let bare = GameConfig::new(200, 200)
+            .with_game_border(false)
+            .with_hud_minimal(true);
+        let (width, height) = fit_board_to_terminal(&bare, 100, 30);
+        assert_eq!((width, height), (49, 27));
+        let game = Game::new(width, height);
+        let buffer = render_sized(&game, &bare, &FrameContext::default(), 100, 30);
+        assert!(!buffer_text(&buffer).contains("Terminal too small!"));
     }
 }
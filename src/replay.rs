@@ -0,0 +1,88 @@
+use crate::config::GameConfig;
+use crate::game::{Direction, Game, GameEvent};
+use crate::power_up::PowerUpKind;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a, which unlike `std`'s hashers is stable across Rust versions, so
+/// a hash can be checked by another build.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Fixed encoding of an event for hashing: a tag byte and a little-endian
+/// payload. Unlike `Debug` output it can't change with a rename, so the tags
+/// must never be reused or renumbered.
+fn event_bytes(event: GameEvent) -> [u8; 5] {
+    let (tag, payload) = match event {
+        GameEvent::None => (0, 0),
+        GameEvent::Moved => (1, 0),
+        GameEvent::Wrapped => (2, 0),
+        GameEvent::FoodEaten { value } => (3, value),
+        GameEvent::BigFoodEaten => (4, 0),
+        GameEvent::Combo(multiplier) => (5, multiplier),
+        GameEvent::PowerUp(PowerUpKind::Slow) => (6, 0),
+        GameEvent::PowerUp(PowerUpKind::Fast) => (6, 1),
+        GameEvent::LifeLost => (7, 0),
+        GameEvent::Respawned => (8, 0),
+        GameEvent::GameOver => (9, 0),
+        GameEvent::Won => (10, 0),
+    };
+    let [a, b, c, d] = payload.to_le_bytes();
+    [tag, a, b, c, d]
+}
+
+/// Replays a game played under `config`'s rules with `seed`, one entry of
+/// `inputs` per tick (`None` for no turn), and hashes every event and the
+/// final board. Identical replays always hash equal, so a submitted score can
+/// be checked against its input log.
+pub fn replay_hash(config: &GameConfig, seed: u64, inputs: &[Option<Direction>]) -> u64 {
+    let mut game = Game::from_config(&config.clone().with_seed(seed));
+    let mut hash = FNV_OFFSET_BASIS;
+
+    for &input in inputs {
+        if let Some(direction) = input {
+            game.set_direction(direction);
+        }
+        let event = game.update();
+        hash = fnv1a(hash, &event_bytes(event));
+    }
+
+    fnv1a(hash, game.to_text_grid().as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_hash_changes_with_inputs() {
+        let config = GameConfig::new(20, 15);
+        let mut inputs = vec![None; 30];
+        inputs[3] = Some(Direction::Down);
+        inputs[8] = Some(Direction::Left);
+
+        let hash = replay_hash(&config, 42, &inputs);
+        assert_eq!(hash, replay_hash(&config, 42, &inputs));
+
+        inputs[8] = Some(Direction::Right);
+        assert_ne!(hash, replay_hash(&config, 42, &inputs));
+    }
+
+    #[test]
+    fn test_event_encoding_is_fixed() {
+        assert_eq!(event_bytes(GameEvent::Moved), [1, 0, 0, 0, 0]);
+        assert_eq!(
+            event_bytes(GameEvent::FoodEaten { value: 300 }),
+            [3, 44, 1, 0, 0]
+        );
+        assert_eq!(
+            event_bytes(GameEvent::PowerUp(PowerUpKind::Fast)),
+            [6, 1, 0, 0, 0]
+        );
+        assert_eq!(event_bytes(GameEvent::Won), [10, 0, 0, 0, 0]);
+    }
+}
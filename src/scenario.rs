@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, io, path::Path};
+
+use crate::game::{Direction, Position};
+
+/// A hand-placed game position to practice from, loaded from a JSON file.
+/// The snake's body is listed head first. Without food, food is placed at
+/// random as usual.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub board_width: u16,
+    pub board_height: u16,
+    pub body: Vec<Position>,
+    pub direction: Direction,
+    #[serde(default)]
+    pub food: Option<Position>,
+    #[serde(default)]
+    pub score: u32,
+}
+
+/// Why a scenario can't be played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScenarioError {
+    EmptyBody,
+    /// A segment or the food lies outside the board.
+    OutOfBounds(Position),
+    /// Two consecutive segments aren't next to each other.
+    Disconnected(Position, Position),
+    /// The body covers this cell more than once.
+    Overlapping(Position),
+    /// The food lies on the snake's body.
+    FoodOnBody(Position),
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioError::EmptyBody => write!(f, "the snake needs at least a head"),
+            ScenarioError::OutOfBounds(pos) => {
+                write!(f, "({}, {}) is outside the board", pos.x, pos.y)
+            }
+            ScenarioError::Disconnected(a, b) => write!(
+                f,
+                "segments ({}, {}) and ({}, {}) aren't next to each other",
+                a.x, a.y, b.x, b.y
+            ),
+            ScenarioError::Overlapping(pos) => {
+                write!(f, "the snake covers ({}, {}) more than once", pos.x, pos.y)
+            }
+            ScenarioError::FoodOnBody(pos) => {
+                write!(f, "the food at ({}, {}) is on the snake", pos.x, pos.y)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+impl Scenario {
+    /// Loads and validates a scenario file.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let scenario: Self = serde_json::from_str(&contents).map_err(io::Error::other)?;
+        scenario
+            .validate()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(scenario)
+    }
+
+    /// Checks that the snake is a connected line of distinct cells on the
+    /// board, and that the food is on the board but off the snake.
+    pub fn validate(&self) -> Result<(), ScenarioError> {
+        if self.body.is_empty() {
+            return Err(ScenarioError::EmptyBody);
+        }
+        let in_bounds = |pos: &Position| pos.x < self.board_width && pos.y < self.board_height;
+        if let Some(&pos) = self
+            .body
+            .iter()
+            .chain(&self.food)
+            .find(|pos| !in_bounds(pos))
+        {
+            return Err(ScenarioError::OutOfBounds(pos));
+        }
+
+        for pair in self.body.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            // Segments on opposite edges are neighbors across a wrap
+            let dx = a.x.abs_diff(b.x);
+            let dy = a.y.abs_diff(b.y);
+            let dx = dx.min(self.board_width - dx);
+            let dy = dy.min(self.board_height - dy);
+            if dx + dy != 1 {
+                return Err(ScenarioError::Disconnected(a, b));
+            }
+        }
+
+        for (i, pos) in self.body.iter().enumerate() {
+            if self.body[..i].contains(pos) {
+                return Err(ScenarioError::Overlapping(*pos));
+            }
+        }
+        if let Some(food) = self.food.filter(|food| self.body.contains(food)) {
+            return Err(ScenarioError::FoodOnBody(food));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disconnected_or_outside_body_is_rejected() {
+        let mut scenario = Scenario {
+            board_width: 10,
+            board_height: 8,
+            body: vec![
+                Position::new(9, 3),
+                Position::new(0, 3),
+                Position::new(0, 4),
+            ],
+            direction: Direction::Right,
+            food: None,
+            score: 0,
+        };
+        assert_eq!(scenario.validate(), Ok(()));
+
+        scenario.body.push(Position::new(2, 4));
+        assert_eq!(
+            scenario.validate(),
+            Err(ScenarioError::Disconnected(
+                Position::new(0, 4),
+                Position::new(2, 4)
+            ))
+        );
+
+        scenario.body = vec![Position::new(10, 3)];
+        assert_eq!(
+            scenario.validate(),
+            Err(ScenarioError::OutOfBounds(Position::new(10, 3)))
+        );
+    }
+
+    #[test]
+    fn test_overlapping_body_or_food_on_body_is_rejected() {
+        // A closed loop comes back to the head's cell
+        let mut scenario = Scenario {
+            board_width: 10,
+            board_height: 8,
+            body: vec![
+                Position::new(2, 2),
+                Position::new(3, 2),
+                Position::new(3, 3),
+                Position::new(2, 3),
+                Position::new(2, 2),
+            ],
+            direction: Direction::Left,
+            food: None,
+            score: 0,
+        };
+        assert_eq!(
+            scenario.validate(),
+            Err(ScenarioError::Overlapping(Position::new(2, 2)))
+        );
+
+        // Doubling back repeats a cell too
+        scenario.body = vec![
+            Position::new(2, 2),
+            Position::new(3, 2),
+            Position::new(2, 2),
+        ];
+        assert_eq!(
+            scenario.validate(),
+            Err(ScenarioError::Overlapping(Position::new(2, 2)))
+        );
+
+        scenario.body.pop();
+        scenario.food = Some(Position::new(3, 2));
+        assert_eq!(
+            scenario.validate(),
+            Err(ScenarioError::FoodOnBody(Position::new(3, 2)))
+        );
+    }
+}
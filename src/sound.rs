@@ -21,10 +21,31 @@ impl SoundSystem for ConsoleSoundSystem {
         }
 
         match event {
-            GameEvent::FoodEaten => {
+            GameEvent::FoodEaten { .. }
+            | GameEvent::BigFoodEaten
+            | GameEvent::Combo(_)
+            | GameEvent::PowerUp(_) => {
                 // Bell sound for eating food
                 print!("\x07");
             }
+            GameEvent::LifeLost | GameEvent::Respawned => {
+                // Two beeps, one short of the game over sound
+                for _ in 0..2 {
+                    print!("\x07");
+                    std::thread::sleep(std::time::Duration::from_millis(100));
+                }
+            }
+            GameEvent::Wrapped => {
+                // Single short bell as the snake comes out the other side
+                print!("\x07");
+            }
+            GameEvent::Won => {
+                // Quick rising run of beeps for winning
+                for _ in 0..5 {
+                    print!("\x07");
+                    std::thread::sleep(std::time::Duration::from_millis(50));
+                }
+            }
             GameEvent::GameOver => {
                 // Multiple beeps for game over
                 for _ in 0..3 {
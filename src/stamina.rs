@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+const MAX_STAMINA: u32 = 100;
+/// Stamina spent per tick while sprinting.
+const DRAIN_PER_TICK: u32 = 4;
+/// Stamina recovered per tick while not sprinting.
+const REGEN_PER_TICK: u32 = 1;
+
+/// Sprint meter: boosting halves the tick interval while stamina lasts and
+/// stamina slowly recovers when not boosting.
+#[derive(Debug)]
+pub struct Stamina {
+    current: u32,
+    boosting: bool,
+}
+
+impl Default for Stamina {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stamina {
+    pub fn new() -> Self {
+        Self {
+            current: MAX_STAMINA,
+            boosting: false,
+        }
+    }
+
+    pub fn toggle_boost(&mut self) {
+        self.boosting = !self.boosting;
+    }
+
+    /// Whether boosting is on and there's stamina left to spend.
+    pub fn is_sprinting(&self) -> bool {
+        self.boosting && self.current > 0
+    }
+
+    /// Remaining stamina as a fraction of the maximum.
+    pub fn ratio(&self) -> f64 {
+        f64::from(self.current) / f64::from(MAX_STAMINA)
+    }
+
+    pub fn tick_rate(&self, base: Duration) -> Duration {
+        if self.is_sprinting() { base / 2 } else { base }
+    }
+
+    /// Drains stamina while sprinting, regenerates it otherwise.
+    pub fn tick(&mut self) {
+        if self.is_sprinting() {
+            self.current = self.current.saturating_sub(DRAIN_PER_TICK);
+        } else {
+            self.current = (self.current + REGEN_PER_TICK).min(MAX_STAMINA);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stamina_depletes_while_boosting_and_regenerates() {
+        let base = Duration::from_millis(100);
+        let mut stamina = Stamina::new();
+        stamina.toggle_boost();
+
+        assert_eq!(stamina.tick_rate(base), Duration::from_millis(50));
+        for _ in 0..10 {
+            stamina.tick();
+        }
+        let drained = stamina.ratio();
+        assert!(drained < 1.0);
+
+        stamina.toggle_boost();
+        assert_eq!(stamina.tick_rate(base), base);
+        stamina.tick();
+        assert!(stamina.ratio() > drained);
+    }
+
+    #[test]
+    fn test_empty_stamina_does_not_sprint() {
+        let base = Duration::from_millis(100);
+        let mut stamina = Stamina::new();
+        stamina.toggle_boost();
+
+        while stamina.is_sprinting() {
+            stamina.tick();
+        }
+
+        assert_eq!(stamina.ratio(), 0.0);
+        assert_eq!(stamina.tick_rate(base), base);
+    }
+}
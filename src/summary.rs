@@ -0,0 +1,80 @@
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::config::GameConfig;
+use crate::game::{Game, GameState};
+
+/// How the game had ended when the summary was taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Won,
+    Lost,
+    /// The player quit while the game was still running or paused.
+    Quit,
+}
+
+/// End-of-game stats printed by `--stats-json` for scripts.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameSummary {
+    pub score: u32,
+    pub length: usize,
+    pub food_eaten: u32,
+    pub duration_secs: f64,
+    pub outcome: Outcome,
+    pub board_width: u16,
+    pub board_height: u16,
+    pub seed: Option<u64>,
+}
+
+impl GameSummary {
+    /// Summarizes `game`, played with `config`, after `duration` of play.
+    pub fn new(game: &Game, config: &GameConfig, duration: Duration) -> Self {
+        let outcome = match game.state() {
+            GameState::Won => Outcome::Won,
+            GameState::GameOver => Outcome::Lost,
+            GameState::Playing | GameState::Paused => Outcome::Quit,
+        };
+
+        Self {
+            score: game.score(),
+            length: game.snake().len(),
+            food_eaten: game.food_eaten(),
+            duration_secs: duration.as_secs_f64(),
+            outcome,
+            board_width: config.board_width,
+            board_height: config.board_height,
+            seed: game.seed(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("summary fields always serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_serializes_final_game_state() {
+        let config = GameConfig::new(12, 8)
+            .with_wall_wrapping(false)
+            .with_seed(21);
+        let mut game = Game::from_config(&config);
+        // Keep heading right until the snake hits the wall
+        while game.state() == GameState::Playing {
+            game.update();
+        }
+
+        let summary = GameSummary::new(&game, &config, Duration::from_millis(1500));
+
+        assert_eq!(summary.outcome, Outcome::Lost);
+        assert_eq!(summary.length, 5 + summary.food_eaten as usize);
+        let json = summary.to_json();
+        assert!(json.contains(r#""outcome":"lost""#));
+        assert!(json.contains(r#""duration_secs":1.5"#));
+        assert!(json.contains(r#""board_width":12,"board_height":8,"seed":21"#));
+    }
+}